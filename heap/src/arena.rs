@@ -0,0 +1,234 @@
+///
+/// slots for node based heaps linked by ids
+///
+/// removed slots are reused, the generation of the slot tells the handles
+/// of the removed item from the handles of the new one, the owner tag
+/// tells them from the handles of other arenas and heaps
+///
+/// `MeldArena` keeps the arenas of melded heaps as groups, a meld moves
+/// groups instead of items so the ids kept by the items stay valid
+///
+use std::collections::HashMap;
+
+use crate::{next_owner, Handle};
+
+#[derive(Debug)]
+struct Cell<N> {
+    item: Option<N>,
+    generation: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct Arena<N> {
+    /// tag of the handles issued by the arena
+    owner: usize,
+    cells: Vec<Cell<N>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<N> Arena<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            owner: next_owner(),
+            cells: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// number of kept items
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// keep item, returns its id
+    pub(crate) fn insert(&mut self, item: N) -> usize {
+        self.insert_with(|_| item)
+    }
+
+    /// keep item made from its own id, returns the id
+    pub(crate) fn insert_with(&mut self, item: impl FnOnce(usize) -> N) -> usize {
+        self.len += 1;
+        match self.free.pop() {
+            Some(id) => {
+                self.cells[id].item = Some(item(id));
+                id
+            }
+            None => {
+                let id = self.cells.len();
+                self.cells.push(Cell {
+                    item: Some(item(id)),
+                    generation: 0,
+                });
+                id
+            }
+        }
+    }
+
+    /// remove item, handles of the id become invalid
+    pub(crate) fn remove(&mut self, id: usize) -> N {
+        let cell = &mut self.cells[id];
+        let item = cell.item.take().expect("vacant arena slot");
+        cell.generation += 1;
+        self.free.push(id);
+        self.len -= 1;
+        item
+    }
+
+    /// handle of the kept item
+    pub(crate) fn handle(&self, id: usize) -> Handle {
+        Handle {
+            owner: self.owner,
+            id,
+            generation: self.cells[id].generation,
+        }
+    }
+
+    /// id of the item behind the handle or None if removed
+    pub(crate) fn id_of(&self, handle: Handle) -> Option<usize> {
+        if handle.owner != self.owner {
+            return None;
+        }
+
+        self.cells
+            .get(handle.id)
+            .filter(|cell| cell.generation == handle.generation && cell.item.is_some())
+            .map(|_| handle.id)
+    }
+}
+
+impl<N> std::ops::Index<usize> for Arena<N> {
+    type Output = N;
+
+    fn index(&self, id: usize) -> &N {
+        self.cells[id].item.as_ref().expect("vacant arena slot")
+    }
+}
+
+impl<N> std::ops::IndexMut<usize> for Arena<N> {
+    fn index_mut(&mut self, id: usize) -> &mut N {
+        self.cells[id].item.as_mut().expect("vacant arena slot")
+    }
+}
+
+/// id of an item in a meld arena, the group is the owner tag of its arena
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Id {
+    group: usize,
+    slot: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct MeldArena<N> {
+    /// group of the new items
+    home: Arena<N>,
+    /// groups of the melded arenas by owner tag, the empty ones are dropped
+    others: HashMap<usize, Arena<N>>,
+    len: usize,
+}
+
+impl<N> MeldArena<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            home: Arena::new(),
+            others: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// number of kept items
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// keep item, returns its id
+    pub(crate) fn insert(&mut self, item: N) -> Id {
+        self.insert_with(|_| item)
+    }
+
+    /// keep item made from its own id, returns the id
+    pub(crate) fn insert_with(&mut self, item: impl FnOnce(Id) -> N) -> Id {
+        let group = self.home.owner;
+        let slot = self.home.insert_with(|slot| item(Id { group, slot }));
+        self.len += 1;
+        Id { group, slot }
+    }
+
+    /// remove item, handles of the id become invalid
+    pub(crate) fn remove(&mut self, id: Id) -> N {
+        self.len -= 1;
+        if id.group == self.home.owner {
+            return self.home.remove(id.slot);
+        }
+
+        let group = self.others.get_mut(&id.group).expect("vacant arena group");
+        let item = group.remove(id.slot);
+        if group.len() == 0 {
+            self.others.remove(&id.group);
+        }
+        item
+    }
+
+    /// handle of the kept item
+    pub(crate) fn handle(&self, id: Id) -> Handle {
+        self.group(id.group)
+            .expect("vacant arena group")
+            .handle(id.slot)
+    }
+
+    /// id of the item behind the handle or None if removed
+    pub(crate) fn id_of(&self, handle: Handle) -> Option<Id> {
+        let slot = self.group(handle.owner)?.id_of(handle)?;
+        Some(Id {
+            group: handle.owner,
+            slot,
+        })
+    }
+
+    /// take over the groups of other, ids and handles of both arenas stay valid;
+    /// the smaller map of groups is moved, O(1) for arenas not melded before
+    pub(crate) fn meld(&mut self, other: MeldArena<N>) {
+        let MeldArena {
+            home,
+            mut others,
+            len,
+        } = other;
+
+        if self.others.len() < others.len() {
+            std::mem::swap(&mut self.others, &mut others);
+        }
+        self.others.extend(others);
+        if home.len() > 0 {
+            self.others.insert(home.owner, home);
+        }
+        self.len += len;
+    }
+
+    fn group(&self, owner: usize) -> Option<&Arena<N>> {
+        if owner == self.home.owner {
+            Some(&self.home)
+        } else {
+            self.others.get(&owner)
+        }
+    }
+}
+
+impl<N> std::ops::Index<Id> for MeldArena<N> {
+    type Output = N;
+
+    fn index(&self, id: Id) -> &N {
+        &self.group(id.group).expect("vacant arena group")[id.slot]
+    }
+}
+
+impl<N> std::ops::IndexMut<Id> for MeldArena<N> {
+    fn index_mut(&mut self, id: Id) -> &mut N {
+        let group = if id.group == self.home.owner {
+            &mut self.home
+        } else {
+            self.others.get_mut(&id.group).expect("vacant arena group")
+        };
+        &mut group[id.slot]
+    }
+}
@@ -0,0 +1,185 @@
+///
+/// dependency free binary format of the heap
+///
+///   fn write_to(writer)             | O(N) |
+///   fn read_from(reader) -> Heap    | O(N) |
+///
+/// the heap is written as the u64 count of the nodes followed by
+/// the value and the priority of every node in the heap order,
+/// numbers are little endian, strings and vectors are prefixed
+/// by their u64 length; handles are not kept, the heap order is
+/// checked when the heap is read
+///
+use std::io::{self, Read, Write};
+
+use crate::{Compare, Heap, Index, Node, OrdF64};
+
+/// encoding of the values and priorities of the binary format
+pub trait Codec: Sized {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+macro_rules! codec_number {
+    ($($number:ty),*) => {
+        $(
+            impl Codec for $number {
+                fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$number>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$number>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+codec_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// written as u64, fails to read on targets where it does not fit
+impl Codec for usize {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u64).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        usize::try_from(u64::decode(reader)?).map_err(|_| invalid_data("usize overflow"))
+    }
+}
+
+impl Codec for bool {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u8).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid bool")),
+        }
+    }
+}
+
+impl Codec for char {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u32).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        char::from_u32(u32::decode(reader)?).ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+impl Codec for () {
+    fn encode<W: Write>(&self, _: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn decode<R: Read>(_: &mut R) -> io::Result<Self> {
+        Ok(())
+    }
+}
+
+impl Codec for OrdF64 {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        f64::decode(reader).map(OrdF64)
+    }
+}
+
+impl Codec for String {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().encode(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let len = usize::decode(reader)?;
+        let mut bytes = vec![];
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        String::from_utf8(bytes).map_err(|_| invalid_data("invalid utf-8"))
+    }
+}
+
+impl<E: Codec> Codec for Vec<E> {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().encode(writer)?;
+        self.iter().try_for_each(|element| element.encode(writer))
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let len = usize::decode(reader)?;
+        // the length is not trusted for the allocation
+        let mut elements = Vec::with_capacity(len.min(PREALLOCATE));
+        for _ in 0..len {
+            elements.push(E::decode(reader)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl<A: Codec, B: Codec> Codec for (A, B) {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.encode(writer)?;
+        self.1.encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok((A::decode(reader)?, B::decode(reader)?))
+    }
+}
+
+impl<T: Codec, P: Codec> Codec for Node<T, P> {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.value.encode(writer)?;
+        self.priority.encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Node {
+            value: T::decode(reader)?,
+            priority: P::decode(reader)?,
+        })
+    }
+}
+
+/// elements allocated up front when reading a length prefixed sequence
+const PREALLOCATE: usize = 4096;
+
+impl<T, const D: Index, C, P> Heap<T, D, C, P>
+where
+    C: Compare<P>,
+    T: Codec,
+    P: Codec,
+{
+    /// write the nodes in the binary format
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().encode(writer)?;
+        self.iter().try_for_each(|node| node.encode(writer))
+    }
+
+    /// read the heap written by write_to, fails with `InvalidData`
+    /// when the nodes break the heap order
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self>
+    where
+        C: Default,
+    {
+        let data = Vec::<Node<T, P>>::decode(reader)?;
+        Self::from_heap_order(data, C::default()).ok_or_else(|| invalid_data("heap order violated"))
+    }
+}
@@ -0,0 +1,337 @@
+///
+/// binomial heap
+///
+/// expected API and performance (worst case):
+///
+///   fn insert(value:T, priority:P) -> Handle     | O(log N)              |
+///   fn meld(other:Self)                          | O(log N)              |
+///   fn peek() -> T                               | O(1)                  |
+///   fn top() -> T                                | O(log N)              |
+///   fn update_by_handle(handle, priority:P)      | O(log N)              |
+///   fn remove_by_handle(handle) -> Node<T, P>    | O(log N)              |
+///
+/// the nodes stay in their own arena so handles survive the swaps of the sift up,
+/// `meld` moves the arena groups instead of the nodes, O(1) for heaps not
+/// melded before and O(G) for G groups left by earlier melds of the smaller side
+///
+use crate::arena::{Id, MeldArena};
+use crate::{Compare, Handle, HeapError, MinOrder, Node, Priority};
+
+#[derive(Debug)]
+struct Item<T, P> {
+    node: Node<T, P>,
+    /// position in the trees
+    tree: Id,
+}
+
+#[derive(Debug)]
+struct Tree {
+    item: Id,
+    parent: Option<Id>,
+    /// child of the highest degree
+    child: Option<Id>,
+    /// next root or next (lower degree) child
+    sibling: Option<Id>,
+    degree: usize,
+}
+
+impl Tree {
+    fn single(item: Id) -> Self {
+        Self {
+            item,
+            parent: None,
+            child: None,
+            sibling: None,
+            degree: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BinomialHeap<T, C = MinOrder, P = Priority> {
+    items: MeldArena<Item<T, P>>,
+    trees: MeldArena<Tree>,
+    /// roots ordered by increasing degree
+    roots: Option<Id>,
+    /// root of the top item
+    top: Option<Id>,
+    cmp: C,
+}
+
+impl<T, C, P> BinomialHeap<T, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            items: MeldArena::new(),
+            trees: MeldArena::new(),
+            roots: None,
+            top: None,
+            cmp,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_none()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.top
+            .map(|top| &self.items[self.trees[top].item].node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.top?;
+        let item = self.trees[top].item;
+        self.detach(item);
+        Some(self.items.remove(item).node.value)
+    }
+
+    /// insert value, the returned handle refers to the node until it leaves the heap
+    pub fn insert(&mut self, value: T, priority: P) -> Handle {
+        let trees = &mut self.trees;
+        let item = self.items.insert_with(|item| Item {
+            node: Node { value, priority },
+            tree: trees.insert(Tree::single(item)),
+        });
+        self.add_root(self.items[item].tree);
+        self.items.handle(item)
+    }
+
+    /// node behind the handle or None if it left the heap
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&Node<T, P>> {
+        self.items.id_of(handle).map(|item| &self.items[item].node)
+    }
+
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T, P>, HeapError> {
+        let item = self.items.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.detach(item);
+        Ok(self.items.remove(item).node)
+    }
+
+    /// change priority of the node behind the handle
+    pub fn update_by_handle(&mut self, handle: Handle, priority: P) -> Result<(), HeapError> {
+        let item = self.items.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        let raise = self.cmp.higher(&priority, &self.items[item].node.priority);
+        self.items[item].node.priority = priority;
+
+        if raise {
+            self.sift_up(self.items[item].tree, false);
+            self.find_top();
+        } else {
+            // the children may be higher now, plant the node again
+            self.detach(item);
+            self.plant(item);
+        }
+
+        Ok(())
+    }
+
+    /// move all nodes of other into the heap, handles of both heaps stay valid
+    pub fn meld(&mut self, other: Self) {
+        self.items.meld(other.items);
+        self.trees.meld(other.trees);
+
+        self.roots = self.union(self.roots, other.roots);
+        self.find_top();
+    }
+
+    fn higher(&self, a: Id, b: Id) -> bool {
+        let a = &self.items[self.trees[a].item].node.priority;
+        let b = &self.items[self.trees[b].item].node.priority;
+        self.cmp.higher(a, b)
+    }
+
+    /// put the item into a new single node tree
+    fn plant(&mut self, item: Id) {
+        let tree = self.trees.insert(Tree::single(item));
+        self.items[item].tree = tree;
+        self.add_root(tree);
+    }
+
+    /// add a single node tree to the roots
+    fn add_root(&mut self, tree: Id) {
+        self.roots = self.union(self.roots, Some(tree));
+        self.find_top();
+    }
+
+    /// take the item out of the trees, the item itself stays in the arena
+    fn detach(&mut self, item: Id) {
+        let root = self.sift_up(self.items[item].tree, true);
+
+        // unlink the root
+        let next = self.trees[root].sibling.take();
+        if self.roots == Some(root) {
+            self.roots = next;
+        } else {
+            let mut prev = self.roots.expect("root in the list");
+            while self.trees[prev].sibling != Some(root) {
+                prev = self.trees[prev].sibling.expect("root in the list");
+            }
+            self.trees[prev].sibling = next;
+        }
+
+        // children are ordered by decreasing degree, reverse them into roots
+        let mut children = None;
+        let mut child = self.trees[root].child;
+        while let Some(current) = child {
+            child = self.trees[current].sibling;
+            self.trees[current].parent = None;
+            self.trees[current].sibling = children;
+            children = Some(current);
+        }
+
+        self.trees.remove(root);
+        self.roots = self.union(self.roots, children);
+        self.find_top();
+    }
+
+    /// swap the item towards the root, all the way up when forced,
+    /// returns the final tree node
+    fn sift_up(&mut self, tree: Id, force: bool) -> Id {
+        let mut tree = tree;
+        while let Some(parent) = self.trees[tree].parent {
+            if !force && !self.higher(tree, parent) {
+                break;
+            }
+
+            let item = self.trees[tree].item;
+            let parent_item = self.trees[parent].item;
+            self.trees[tree].item = parent_item;
+            self.trees[parent].item = item;
+            self.items[parent_item].tree = tree;
+            self.items[item].tree = parent;
+
+            tree = parent;
+        }
+
+        tree
+    }
+
+    fn find_top(&mut self) {
+        self.top = self.roots;
+
+        let mut root = self.roots;
+        while let Some(current) = root {
+            if self.higher(current, self.top.unwrap()) {
+                self.top = Some(current);
+            }
+            root = self.trees[current].sibling;
+        }
+    }
+
+    /// the lower root becomes the first child of the other one
+    fn link(&mut self, child: Id, parent: Id) {
+        self.trees[child].parent = Some(parent);
+        self.trees[child].sibling = self.trees[parent].child;
+        self.trees[parent].child = Some(child);
+        self.trees[parent].degree += 1;
+    }
+
+    /// merge two root lists ordered by degree
+    fn merge_roots(&mut self, a: Option<Id>, b: Option<Id>) -> Option<Id> {
+        let (mut a, mut b) = (a, b);
+        let mut head = None;
+        let mut tail: Option<Id> = None;
+
+        loop {
+            let next = match (a, b) {
+                (Some(x), Some(y)) => {
+                    if self.trees[x].degree <= self.trees[y].degree {
+                        a = self.trees[x].sibling;
+                        x
+                    } else {
+                        b = self.trees[y].sibling;
+                        y
+                    }
+                }
+                (Some(x), None) | (None, Some(x)) => {
+                    match tail {
+                        Some(tail) => self.trees[tail].sibling = Some(x),
+                        None => head = Some(x),
+                    }
+                    return head;
+                }
+                (None, None) => return head,
+            };
+
+            match tail {
+                Some(tail) => self.trees[tail].sibling = Some(next),
+                None => head = Some(next),
+            }
+            tail = Some(next);
+        }
+    }
+
+    /// merge two root lists linking trees of the same degree
+    fn union(&mut self, a: Option<Id>, b: Option<Id>) -> Option<Id> {
+        let mut head = self.merge_roots(a, b);
+        let mut current = head?;
+
+        let mut prev: Option<Id> = None;
+        while let Some(next) = self.trees[current].sibling {
+            let degree = self.trees[current].degree;
+            let three_in_row = self.trees[next]
+                .sibling
+                .is_some_and(|after| self.trees[after].degree == degree);
+
+            if self.trees[next].degree != degree || three_in_row {
+                prev = Some(current);
+                current = next;
+            } else if !self.higher(next, current) {
+                self.trees[current].sibling = self.trees[next].sibling;
+                self.link(next, current);
+            } else {
+                match prev {
+                    Some(prev) => self.trees[prev].sibling = Some(next),
+                    None => head = Some(next),
+                }
+                self.link(current, next);
+                current = next;
+            }
+        }
+
+        head
+    }
+} // BinomialHeap
+
+impl<T, C, P> Default for BinomialHeap<T, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,100 @@
+///
+/// capacity bounded heap keeping the K highest nodes
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:P) -> Option<Node>  | O(log K), O(1) when rejected |
+///   fn worst() -> Option<&Node>                     | O(1)                         |
+///   fn into_sorted_vec() -> Vec<Node>               | O(K log K), highest first    |
+///
+/// the nodes are kept in a heap of the reversed order, the lowest kept node
+/// is on the top so a new node is compared against it with one comparison
+///
+use std::mem;
+
+use crate::{Compare, Heap, Index, MinOrder, Node, Priority, Reversed, TOP};
+
+#[derive(Debug)]
+pub struct BoundedHeap<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    C: Compare<P>,
+{
+    heap: Heap<T, D, Reversed<C>, P>,
+    capacity: usize,
+}
+
+impl<T, const D: Index, C, P> BoundedHeap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap keeping at most capacity nodes
+    pub fn new(capacity: usize) -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(capacity, C::default())
+    }
+
+    /// create new heap keeping at most capacity nodes ordered by cmp
+    pub fn with_compare(capacity: usize, cmp: C) -> Self {
+        Self {
+            heap: Heap::with_compare(Reversed(cmp)),
+            capacity,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// maximal number of parameters
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// check is heap full, next insert evicts a node
+    pub fn is_full(&self) -> bool {
+        self.heap.len() >= self.capacity
+    }
+
+    /// the lowest kept node, the one evicted next
+    pub fn worst(&self) -> Option<&Node<T, P>> {
+        self.heap.data.get(TOP).map(|slot| &slot.node)
+    }
+
+    /// insert value, when the heap is full the lowest node is evicted and returned,
+    /// it is the inserted node itself when it is not higher than the kept ones
+    pub fn insert(&mut self, value: T, priority: P) -> Option<Node<T, P>> {
+        if !self.is_full() {
+            self.heap.insert(value, priority);
+            return None;
+        }
+
+        let node = Node { value, priority };
+        // the heap compares in reversed order, the inner order tells which node is higher
+        let higher = match self.worst() {
+            Some(worst) => self.heap.cmp.0.higher(&node.priority, &worst.priority),
+            // zero capacity
+            None => false,
+        };
+        if !higher {
+            return Some(node);
+        }
+
+        let mut worst = self.heap.peek_mut()?;
+        Some(mem::replace(&mut *worst, node))
+    }
+
+    /// consume the heap, nodes sorted in priority order (highest first)
+    pub fn into_sorted_vec(self) -> Vec<Node<T, P>> {
+        let mut nodes = self.heap.into_sorted_vec();
+        nodes.reverse();
+        nodes
+    }
+} // BoundedHeap
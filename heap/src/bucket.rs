@@ -0,0 +1,206 @@
+///
+/// bucket queue for small integer priorities 0..=max_priority
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:Priority) -> Handle   | O(1)                      |
+///   fn peek() -> T                                    | O(1)                      |
+///   fn top() -> T                                     | O(1) amortized            |
+///   fn update_by_handle(handle, priority:Priority)    | O(1) amortized            |
+///   fn remove_by_handle(handle) -> Node               | O(1) amortized            |
+///   fn update(value:T, priority:Priority)             | O(N)                      |
+///   fn remove(value:T) -> Node                        | O(N)                      |
+///
+/// the lowest priority is on the top, a removal emptying the lowest bucket
+/// moves on to the next non empty one, the amortized bounds hold while inserted
+/// priorities do not go below the last removed one (Dial's algorithm),
+/// otherwise a removal scans up to max_priority buckets
+///
+use crate::arena::Arena;
+use crate::{Handle, HeapError, Node, Priority};
+
+#[derive(Debug)]
+struct Item<T> {
+    node: Node<T>,
+    /// position in the bucket of the priority
+    slot: usize,
+}
+
+#[derive(Debug)]
+pub struct BucketQueue<T> {
+    items: Arena<Item<T>>,
+    /// item ids by priority
+    buckets: Vec<Vec<usize>>,
+    /// the lowest non empty bucket or the last one for empty queue
+    lowest: usize,
+}
+
+impl<T> BucketQueue<T> {
+    /// create new queue for priorities up to max_priority (inclusive)
+    pub fn new(max_priority: Priority) -> Self {
+        let buckets = usize::try_from(max_priority)
+            .ok()
+            .and_then(|max| max.checked_add(1))
+            .expect("max priority too large");
+
+        Self {
+            items: Arena::new(),
+            buckets: (0..buckets).map(|_| Vec::new()).collect(),
+            lowest: buckets - 1,
+        }
+    }
+
+    /// check is queue empty
+    pub fn is_empty(&self) -> bool {
+        self.items.len() == 0
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// highest priority accepted by the queue
+    pub fn max_priority(&self) -> Priority {
+        (self.buckets.len() - 1) as Priority
+    }
+
+    /// return copy of the top value, panics on empty queue
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty queue
+    pub fn try_peek(&self) -> Option<&T> {
+        self.buckets[self.lowest]
+            .last()
+            .map(|&id| &self.items[id].node.value)
+    }
+
+    /// remove and return the top value, panics on empty queue
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty queue
+    pub fn pop(&mut self) -> Option<T> {
+        let id = *self.buckets[self.lowest].last()?;
+        Some(self.remove_at(id).value)
+    }
+
+    /// insert value, fails when the priority is above max_priority,
+    /// the returned handle refers to the node until it leaves the queue
+    pub fn insert(&mut self, value: T, priority: Priority) -> Result<Handle, HeapError> {
+        let bucket = self.bucket_of(priority)?;
+        let id = self.items.insert(Item {
+            node: Node { value, priority },
+            slot: 0,
+        });
+        self.put(id, bucket);
+        Ok(self.items.handle(id))
+    }
+
+    /// node behind the handle or None if it left the queue
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&Node<T>> {
+        self.items.id_of(handle).map(|id| &self.items[id].node)
+    }
+
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T>, HeapError> {
+        let id = self.items.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        Ok(self.remove_at(id))
+    }
+
+    /// change priority of the node behind the handle, fails when above max_priority
+    pub fn update_by_handle(
+        &mut self,
+        handle: Handle,
+        priority: Priority,
+    ) -> Result<(), HeapError> {
+        let id = self.items.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.update_at(id, priority)
+    }
+
+    fn bucket_of(&self, priority: Priority) -> Result<usize, HeapError> {
+        if priority > self.max_priority() {
+            return Err(HeapError::OutOfRange);
+        }
+        Ok(priority as usize)
+    }
+
+    fn update_at(&mut self, id: usize, priority: Priority) -> Result<(), HeapError> {
+        let bucket = self.bucket_of(priority)?;
+        self.take(id);
+        self.items[id].node.priority = priority;
+        self.put(id, bucket);
+        self.advance();
+        Ok(())
+    }
+
+    fn remove_at(&mut self, id: usize) -> Node<T> {
+        self.take(id);
+        let node = self.items.remove(id).node;
+        self.advance();
+        node
+    }
+
+    /// put the item to the end of the bucket
+    fn put(&mut self, id: usize, bucket: usize) {
+        self.items[id].slot = self.buckets[bucket].len();
+        self.buckets[bucket].push(id);
+        self.lowest = self.lowest.min(bucket);
+    }
+
+    /// move lowest up to the next non empty bucket
+    fn advance(&mut self) {
+        while self.lowest + 1 < self.buckets.len() && self.buckets[self.lowest].is_empty() {
+            self.lowest += 1;
+        }
+    }
+
+    /// take the item out of its bucket, the item itself stays in the arena
+    fn take(&mut self, id: usize) {
+        let bucket = self.items[id].node.priority as usize;
+        let slot = self.items[id].slot;
+        self.buckets[bucket].swap_remove(slot);
+        if let Some(&moved) = self.buckets[bucket].get(slot) {
+            self.items[moved].slot = slot;
+        }
+    }
+} // BucketQueue
+
+impl<T> BucketQueue<T>
+where
+    T: PartialEq,
+{
+    /// remove the node keeping value, returns the removed node
+    pub fn remove(&mut self, value: T) -> Result<Node<T>, HeapError> {
+        if self.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let id = self.find(&value).ok_or(HeapError::NotFound)?;
+        Ok(self.remove_at(id))
+    }
+
+    /// change priority of the node keeping value, fails when above max_priority
+    pub fn update(&mut self, value: T, priority: Priority) -> Result<(), HeapError> {
+        if self.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let id = self.find(&value).ok_or(HeapError::NotFound)?;
+        self.update_at(id, priority)
+    }
+
+    fn find(&self, value: &T) -> Option<usize> {
+        self.buckets
+            .iter()
+            .flatten()
+            .copied()
+            .find(|&id| self.items[id].node.value == *value)
+    }
+}
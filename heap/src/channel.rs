@@ -0,0 +1,218 @@
+///
+/// multi producer single consumer channel delivering the highest priority first
+///
+/// expected API and performance:
+///
+///   fn send(value:T, priority:P)     | O(log N) |
+///   fn recv() -> T                   | O(log N), blocks while empty |
+///   fn try_recv() -> T               | O(log N) |
+///   fn recv_timeout(timeout) -> T    | O(log N), blocks up to timeout |
+///
+/// disconnects like `std::sync::mpsc`: sending fails once the receiver is
+/// dropped, receiving fails once all senders are dropped and nothing is pending
+///
+use std::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{Compare, Heap, Index, MinOrder, Node, Priority};
+
+#[derive(Debug)]
+struct State<T, const D: Index, C, P>
+where
+    C: Compare<P>,
+{
+    heap: Heap<T, D, C, P>,
+    senders: usize,
+    receiver: bool,
+}
+
+#[derive(Debug)]
+struct Shared<T, const D: Index, C, P>
+where
+    C: Compare<P>,
+{
+    state: Mutex<State<T, D, C, P>>,
+    ready: Condvar,
+}
+
+impl<T, const D: Index, C, P> Shared<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// lock ignoring poisoning, a panic in another thread leaves the heap valid
+    fn lock(&self) -> MutexGuard<'_, State<T, D, C, P>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// sending half of the channel, cloned for more producers
+#[derive(Debug)]
+pub struct Sender<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    C: Compare<P>,
+{
+    shared: Arc<Shared<T, D, C, P>>,
+}
+
+/// receiving half of the channel
+#[derive(Debug)]
+pub struct Receiver<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    C: Compare<P>,
+{
+    shared: Arc<Shared<T, D, C, P>>,
+}
+
+/// create new channel, the lowest priority is received first
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    channel_with_compare(MinOrder)
+}
+
+/// create new channel ordered by cmp
+pub fn channel_with_compare<T, const D: Index, C, P>(
+    cmp: C,
+) -> (Sender<T, D, C, P>, Receiver<T, D, C, P>)
+where
+    C: Compare<P>,
+{
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            heap: Heap::with_compare(cmp),
+            senders: 1,
+            receiver: true,
+        }),
+        ready: Condvar::new(),
+    });
+
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+impl<T, const D: Index, C, P> Sender<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// send value, fails returning the node when the receiver is dropped
+    pub fn send(&self, value: T, priority: P) -> Result<(), SendError<Node<T, P>>> {
+        let mut state = self.shared.lock();
+        if !state.receiver {
+            return Err(SendError(Node { value, priority }));
+        }
+
+        state.heap.insert(value, priority);
+        drop(state);
+        self.shared.ready.notify_one();
+        Ok(())
+    }
+}
+
+impl<T, const D: Index, C, P> Clone for Sender<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T, const D: Index, C, P> Drop for Sender<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            // wake up the receiver to see the disconnection
+            self.shared.ready.notify_all();
+        }
+    }
+}
+
+impl<T, const D: Index, C, P> Receiver<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// receive the highest pending value, blocks while nothing is pending,
+    /// fails when all senders are dropped and nothing is pending
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(value) = state.heap.pop() {
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+
+            state = self
+                .shared
+                .ready
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// receive the highest pending value without blocking
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.heap.pop() {
+            Some(value) => Ok(value),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// receive the highest pending value, blocks up to timeout while nothing is pending
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            // too far to represent, the same as no timeout
+            return self.recv().map_err(|_| RecvTimeoutError::Disconnected);
+        };
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(value) = state.heap.pop() {
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+
+            state = self
+                .shared
+                .ready
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+}
+
+impl<T, const D: Index, C, P> Drop for Receiver<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver = false;
+        // pending values are never received, dropped outside the lock
+        // as they may keep senders of the channel
+        let pending: Vec<Node<T, P>> = state.heap.drain().collect();
+        drop(state);
+        drop(pending);
+    }
+}
@@ -0,0 +1,179 @@
+///
+/// thread safe priority queue sharded over several heaps (multi-queue)
+///
+/// expected API and performance, S shards:
+///
+///   fn push(value:T, priority:P)   | O(log N/S) |
+///   fn try_pop() -> Option<T>      | O(log N/S) |
+///   fn pop_wait() -> T             | blocks until a value is pushed |
+///
+/// push goes to the next free shard, pop compares the tops of two shards
+/// picked at random and takes the higher one, so threads rarely wait for
+/// the same lock; the order is relaxed, the popped value is one of the
+/// highest but not always the top (it is with a single shard)
+///
+/// share it between threads by `Arc` or scoped threads, all methods take `&self`
+///
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::{Compare, Heap, Index, MinOrder, Priority, TOP};
+
+#[derive(Debug)]
+pub struct ConcurrentHeap<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    C: Compare<P>,
+{
+    shards: Vec<Mutex<Heap<T, D, C, P>>>,
+    len: AtomicUsize,
+    /// next shard for push
+    next: AtomicUsize,
+    /// state of the random shard picks
+    seed: AtomicU64,
+    /// number of threads blocked in pop_wait
+    sleepers: AtomicUsize,
+    signal: Mutex<()>,
+    ready: Condvar,
+}
+
+/// lock ignoring poisoning, a panic in another thread leaves the heap valid
+fn lock<H>(mutex: &Mutex<H>) -> MutexGuard<'_, H> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T, const D: Index, C, P> ConcurrentHeap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap of shards heaps, panics for zero shards
+    pub fn new(shards: usize) -> Self
+    where
+        C: Default,
+    {
+        Self::from_shards((0..shards).map(|_| Heap::new()).collect())
+    }
+
+    /// create new heap of shards heaps ordered by cmp, panics for zero shards
+    pub fn with_compare(shards: usize, cmp: C) -> Self
+    where
+        C: Clone,
+    {
+        Self::from_shards(
+            (0..shards)
+                .map(|_| Heap::with_compare(cmp.clone()))
+                .collect(),
+        )
+    }
+
+    fn from_shards(shards: Vec<Heap<T, D, C, P>>) -> Self {
+        assert!(!shards.is_empty(), "at least one shard");
+        Self {
+            shards: shards.into_iter().map(Mutex::new).collect(),
+            len: AtomicUsize::new(0),
+            next: AtomicUsize::new(0),
+            seed: AtomicU64::new(0),
+            sleepers: AtomicUsize::new(0),
+            signal: Mutex::new(()),
+            ready: Condvar::new(),
+        }
+    }
+
+    /// check is heap empty, a snapshot while other threads push and pop
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// number of parameters, a snapshot while other threads push and pop
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    /// number of shards
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// insert value and wake up one thread blocked in pop_wait
+    pub fn push(&self, value: T, priority: P) {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut shard = (0..self.shards.len())
+            .map(|i| (start + i) % self.shards.len())
+            .find_map(|i| self.shards[i].try_lock().ok())
+            .unwrap_or_else(|| lock(&self.shards[start % self.shards.len()]));
+
+        shard.insert(value, priority);
+        // counted under the lock, before any pop of the value can count it out
+        self.len.fetch_add(1, Ordering::SeqCst);
+        drop(shard);
+
+        if self.sleepers.load(Ordering::SeqCst) > 0 {
+            let _signal = lock(&self.signal);
+            self.ready.notify_one();
+        }
+    }
+
+    /// remove and return one of the highest values or None for empty heap
+    pub fn try_pop(&self) -> Option<T> {
+        let (a, b) = (self.pick(), self.pick());
+        let value = self.pop_higher(a.min(b), a.max(b)).or_else(|| {
+            // both picks empty, any kept value will do
+            self.shards.iter().find_map(|shard| lock(shard).pop())
+        })?;
+
+        self.len.fetch_sub(1, Ordering::SeqCst);
+        Some(value)
+    }
+
+    /// remove and return one of the highest values, blocks while the heap is empty
+    pub fn pop_wait(&self) -> T {
+        loop {
+            if let Some(value) = self.try_pop() {
+                return value;
+            }
+
+            let mut signal = lock(&self.signal);
+            // pairs with the check of sleepers in push, one of them sees the other
+            self.sleepers.fetch_add(1, Ordering::SeqCst);
+            while self.len.load(Ordering::SeqCst) == 0 {
+                signal = self
+                    .ready
+                    .wait(signal)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            self.sleepers.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// pop the higher top of the shards a <= b, locked in index order
+    fn pop_higher(&self, a: usize, b: usize) -> Option<T> {
+        let mut first = lock(&self.shards[a]);
+        if a == b {
+            return first.pop();
+        }
+
+        let mut second = lock(&self.shards[b]);
+        let take_second = match (first.data.get(TOP), second.data.get(TOP)) {
+            (Some(x), Some(y)) => first.cmp.higher(&y.node.priority, &x.node.priority),
+            (None, _) => true,
+            (Some(_), None) => false,
+        };
+
+        if take_second {
+            second.pop()
+        } else {
+            first.pop()
+        }
+    }
+
+    /// random shard, splitmix64 of a shared counter
+    fn pick(&self) -> usize {
+        let mut z = self
+            .seed
+            .fetch_add(0x9e3779b97f4a7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e3779b97f4a7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        (z % self.shards.len() as u64) as usize
+    }
+} // ConcurrentHeap
@@ -0,0 +1,345 @@
+///
+/// Fibonacci heap
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:P) -> Handle          | O(1)               |
+///   fn meld(other:Self)                               | O(1)               |
+///   fn peek() -> T                                    | O(1)               |
+///   fn top() -> T                                     | O(log N) amortized |
+///   fn update_by_handle(handle, priority:P)  raise    | O(1) amortized     |
+///   fn update_by_handle(handle, priority:P)  lower    | O(log N) amortized |
+///   fn remove_by_handle(handle) -> Node<T, P>         | O(log N) amortized |
+///
+/// nodes are kept in an arena and linked by ids into circular sibling lists,
+/// `meld` moves the arena groups instead of the nodes, O(1) for heaps not
+/// melded before and O(G) for G groups left by earlier melds of the smaller side
+///
+use crate::arena::{Id, MeldArena};
+use crate::{Compare, Handle, HeapError, MinOrder, Node, Priority};
+
+#[derive(Debug)]
+struct FibNode<T, P> {
+    node: Node<T, P>,
+    parent: Option<Id>,
+    /// any child, the children make a circular list
+    child: Option<Id>,
+    left: Id,
+    right: Id,
+    degree: usize,
+    /// lost a child since it became a child itself
+    marked: bool,
+}
+
+#[derive(Debug)]
+pub struct FibonacciHeap<T, C = MinOrder, P = Priority> {
+    arena: MeldArena<FibNode<T, P>>,
+    /// the highest root, roots make a circular list
+    top: Option<Id>,
+    cmp: C,
+}
+
+impl<T, C, P> FibonacciHeap<T, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            arena: MeldArena::new(),
+            top: None,
+            cmp,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.top.is_none()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.top.map(|top| &self.arena[top].node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.top?;
+        Some(self.remove_at(top).value)
+    }
+
+    /// insert value, the returned handle refers to the node until it leaves the heap
+    pub fn insert(&mut self, value: T, priority: P) -> Handle {
+        let id = self.arena.insert_with(|id| FibNode {
+            node: Node { value, priority },
+            parent: None,
+            child: None,
+            left: id,
+            right: id,
+            degree: 0,
+            marked: false,
+        });
+
+        self.add_root(id);
+        self.arena.handle(id)
+    }
+
+    /// node behind the handle or None if it left the heap
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&Node<T, P>> {
+        self.arena.id_of(handle).map(|id| &self.arena[id].node)
+    }
+
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T, P>, HeapError> {
+        let id = self.arena.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        Ok(self.remove_at(id))
+    }
+
+    /// change priority of the node behind the handle
+    pub fn update_by_handle(&mut self, handle: Handle, priority: P) -> Result<(), HeapError> {
+        let id = self.arena.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        let raise = self.cmp.higher(&priority, &self.arena[id].node.priority);
+        self.arena[id].node.priority = priority;
+
+        if raise {
+            // decrease-key
+            if let Some(parent) = self.arena[id].parent {
+                if self.higher(id, parent) {
+                    self.cut(id);
+                    self.cascading_cut(parent);
+                }
+            }
+            if self.arena[id].parent.is_none() && self.higher(id, self.top.unwrap()) {
+                self.top = Some(id);
+            }
+        } else {
+            // children may be higher now, all of them become roots
+            let was_top = self.top == Some(id);
+            self.make_root(id);
+            self.move_children_to_roots(id);
+            if was_top {
+                self.consolidate();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// move all nodes of other into the heap, handles of both heaps stay valid
+    pub fn meld(&mut self, other: Self) {
+        self.arena.meld(other.arena);
+
+        if let Some(other_top) = other.top {
+            match self.top {
+                None => self.top = Some(other_top),
+                Some(top) => {
+                    self.splice(top, other_top);
+                    if self.higher(other_top, top) {
+                        self.top = Some(other_top);
+                    }
+                }
+            }
+        }
+    }
+
+    fn higher(&self, a: Id, b: Id) -> bool {
+        self.cmp
+            .higher(&self.arena[a].node.priority, &self.arena[b].node.priority)
+    }
+
+    fn remove_at(&mut self, id: Id) -> Node<T, P> {
+        let was_top = self.top == Some(id);
+        self.make_root(id);
+        self.move_children_to_roots(id);
+
+        let next = self.arena[id].right;
+        self.unlink(id);
+
+        if was_top {
+            if next == id {
+                self.top = None;
+            } else {
+                self.top = Some(next);
+                self.consolidate();
+            }
+        }
+
+        self.arena.remove(id).node
+    }
+
+    /// join two circular lists
+    fn splice(&mut self, a: Id, b: Id) {
+        let a_right = self.arena[a].right;
+        let b_left = self.arena[b].left;
+
+        self.arena[a].right = b;
+        self.arena[b].left = a;
+        self.arena[b_left].right = a_right;
+        self.arena[a_right].left = b_left;
+    }
+
+    /// take the node out of its circular list
+    fn unlink(&mut self, id: Id) {
+        let left = self.arena[id].left;
+        let right = self.arena[id].right;
+
+        self.arena[left].right = right;
+        self.arena[right].left = left;
+        self.arena[id].left = id;
+        self.arena[id].right = id;
+    }
+
+    /// put a single node into the root list
+    fn add_root(&mut self, id: Id) {
+        match self.top {
+            None => self.top = Some(id),
+            Some(top) => {
+                self.splice(top, id);
+                if self.higher(id, top) {
+                    self.top = Some(id);
+                }
+            }
+        }
+    }
+
+    /// cut the node from its parent, cascading through marked ancestors
+    fn make_root(&mut self, id: Id) {
+        if let Some(parent) = self.arena[id].parent {
+            self.cut(id);
+            self.cascading_cut(parent);
+        }
+    }
+
+    /// move the node with its subtree from the parent into the root list
+    fn cut(&mut self, id: Id) {
+        let parent = self.arena[id].parent.take().expect("node is a root");
+        let right = self.arena[id].right;
+
+        if self.arena[parent].child == Some(id) {
+            self.arena[parent].child = if right == id { None } else { Some(right) };
+        }
+        self.arena[parent].degree -= 1;
+
+        self.unlink(id);
+        self.arena[id].marked = false;
+        self.splice(self.top.expect("heap with a child has a top"), id);
+    }
+
+    fn cascading_cut(&mut self, id: Id) {
+        let mut id = id;
+        while let Some(parent) = self.arena[id].parent {
+            if !self.arena[id].marked {
+                self.arena[id].marked = true;
+                return;
+            }
+            self.cut(id);
+            id = parent;
+        }
+    }
+
+    fn move_children_to_roots(&mut self, id: Id) {
+        if let Some(child) = self.arena[id].child.take() {
+            let mut current = child;
+            loop {
+                self.arena[current].parent = None;
+                self.arena[current].marked = false;
+                current = self.arena[current].right;
+                if current == child {
+                    break;
+                }
+            }
+
+            self.arena[id].degree = 0;
+            self.splice(id, child);
+        }
+    }
+
+    /// the lower root becomes a child of the other one
+    fn link(&mut self, a: Id, b: Id) -> Id {
+        let (parent, child) = if self.higher(b, a) { (b, a) } else { (a, b) };
+
+        self.arena[child].parent = Some(parent);
+        self.arena[child].marked = false;
+        match self.arena[parent].child {
+            None => self.arena[parent].child = Some(child),
+            Some(first) => self.splice(first, child),
+        }
+        self.arena[parent].degree += 1;
+
+        parent
+    }
+
+    /// link roots of the same degree until all degrees differ, find the new top
+    fn consolidate(&mut self) {
+        let Some(start) = self.top.take() else {
+            return;
+        };
+
+        let mut roots = vec![start];
+        let mut current = self.arena[start].right;
+        while current != start {
+            roots.push(current);
+            current = self.arena[current].right;
+        }
+
+        let mut by_degree: Vec<Option<Id>> = Vec::new();
+        for root in roots {
+            self.unlink(root);
+
+            let mut root = root;
+            loop {
+                let degree = self.arena[root].degree;
+                if by_degree.len() <= degree {
+                    by_degree.resize(degree + 1, None);
+                }
+
+                match by_degree[degree].take() {
+                    Some(other) => root = self.link(root, other),
+                    None => {
+                        by_degree[degree] = Some(root);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for root in by_degree.into_iter().flatten() {
+            self.add_root(root);
+        }
+    }
+} // FibonacciHeap
+
+impl<T, C, P> Default for FibonacciHeap<T, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,234 @@
+///
+/// graph algorithms driven by `Heap` handles
+///
+/// expected API and performance, V vertices, E edges:
+///
+///   fn dijkstra(graph, source) -> Paths                      | O(E log V) |
+///   fn astar(graph, source, target, heuristic) -> Option<..> | O(E log V) |
+///   fn prim(graph) -> Vec<Edge>                              | O(E log V) |
+///
+/// the arity D of the heap is a parameter of every algorithm,
+/// `dijkstra::<_, 4>(&graph, 0)` runs on a 4-ary heap
+///
+use crate::{Handle, Heap, Index, Node, PeekMut, Priority};
+
+/// vertices 0..vertices() and weighted edges of a graph
+pub trait Adjacency {
+    /// number of vertices
+    fn vertices(&self) -> usize;
+
+    /// edges leaving the vertex as (target, weight)
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = (usize, Priority)> + '_;
+}
+
+/// adjacency lists, `graph[vertex]` keeps the (target, weight) edges
+impl Adjacency for [Vec<(usize, Priority)>] {
+    fn vertices(&self) -> usize {
+        self.len()
+    }
+
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = (usize, Priority)> + '_ {
+        self[vertex].iter().copied()
+    }
+}
+
+impl Adjacency for Vec<Vec<(usize, Priority)>> {
+    fn vertices(&self) -> usize {
+        self.len()
+    }
+
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = (usize, Priority)> + '_ {
+        self[vertex].iter().copied()
+    }
+}
+
+/// edge of a spanning tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: Priority,
+}
+
+/// shortest paths from one source
+#[derive(Debug, Clone)]
+pub struct Paths {
+    distances: Vec<Option<Priority>>,
+    parents: Vec<Option<usize>>,
+}
+
+impl Paths {
+    /// length of the shortest path to the vertex or None if unreachable
+    pub fn distance(&self, vertex: usize) -> Option<Priority> {
+        self.distances[vertex]
+    }
+
+    /// vertices of the shortest path from the source to the vertex
+    pub fn path_to(&self, vertex: usize) -> Option<Vec<usize>> {
+        self.distances[vertex]?;
+        Some(walk_back(&self.parents, vertex))
+    }
+}
+
+/// path to the vertex following the parents up to the source
+fn walk_back(parents: &[Option<usize>], vertex: usize) -> Vec<usize> {
+    let mut path = vec![vertex];
+    while let Some(parent) = parents[*path.last().unwrap()] {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+/// open vertices of a search, a vertex keeps its handle while in the heap
+struct Frontier<const D: Index> {
+    heap: Heap<usize, D>,
+    handles: Vec<Option<Handle>>,
+}
+
+impl<const D: Index> Frontier<D> {
+    fn new(vertices: usize) -> Self {
+        Self {
+            heap: Heap::with_capacity(vertices),
+            handles: vec![None; vertices],
+        }
+    }
+
+    /// insert the vertex or lower its priority if it is open already
+    fn push(&mut self, vertex: usize, priority: Priority) {
+        match self.handles[vertex] {
+            Some(handle) => {
+                self.heap
+                    .update_by_handle(handle, priority)
+                    .expect("open vertex in the heap");
+            }
+            None => self.handles[vertex] = Some(self.heap.insert(vertex, priority)),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Node<usize>> {
+        let node = PeekMut::pop(self.heap.peek_mut()?);
+        self.handles[node.value] = None;
+        Some(node)
+    }
+}
+
+/// shortest paths from the source, weights are not negative by the type
+pub fn dijkstra<G, const D: Index>(graph: &G, source: usize) -> Paths
+where
+    G: Adjacency + ?Sized,
+{
+    let vertices = graph.vertices();
+    let mut distances: Vec<Option<Priority>> = vec![None; vertices];
+    let mut parents = vec![None; vertices];
+    let mut done = vec![false; vertices];
+    let mut frontier = Frontier::<D>::new(vertices);
+
+    distances[source] = Some(0);
+    frontier.push(source, 0);
+
+    while let Some(Node {
+        value: vertex,
+        priority: distance,
+    }) = frontier.pop()
+    {
+        done[vertex] = true;
+        for (target, weight) in graph.edges(vertex) {
+            let candidate = distance.saturating_add(weight);
+            if done[target] || distances[target].is_some_and(|known| known <= candidate) {
+                continue;
+            }
+
+            distances[target] = Some(candidate);
+            parents[target] = Some(vertex);
+            frontier.push(target, candidate);
+        }
+    }
+
+    Paths { distances, parents }
+}
+
+/// shortest path from the source to the target guided by the heuristic,
+/// an estimate of the remaining distance which must not overestimate it,
+/// returns the length and the vertices of the path or None if unreachable
+pub fn astar<G, const D: Index>(
+    graph: &G,
+    source: usize,
+    target: usize,
+    heuristic: impl Fn(usize) -> Priority,
+) -> Option<(Priority, Vec<usize>)>
+where
+    G: Adjacency + ?Sized,
+{
+    let vertices = graph.vertices();
+    let mut distances: Vec<Option<Priority>> = vec![None; vertices];
+    let mut parents = vec![None; vertices];
+    let mut frontier = Frontier::<D>::new(vertices);
+
+    distances[source] = Some(0);
+    frontier.push(source, heuristic(source));
+
+    while let Some(Node { value: vertex, .. }) = frontier.pop() {
+        let distance = distances[vertex].expect("open vertex has a distance");
+        if vertex == target {
+            return Some((distance, walk_back(&parents, vertex)));
+        }
+
+        for (next, weight) in graph.edges(vertex) {
+            let candidate = distance.saturating_add(weight);
+            if distances[next].is_some_and(|known| known <= candidate) {
+                continue;
+            }
+
+            // an inconsistent heuristic may reopen a closed vertex
+            distances[next] = Some(candidate);
+            parents[next] = Some(vertex);
+            frontier.push(next, candidate.saturating_add(heuristic(next)));
+        }
+    }
+
+    None
+}
+
+/// minimum spanning forest of an undirected graph,
+/// every edge has to be listed by both of its vertices
+pub fn prim<G, const D: Index>(graph: &G) -> Vec<Edge>
+where
+    G: Adjacency + ?Sized,
+{
+    let vertices = graph.vertices();
+    let mut tree = Vec::with_capacity(vertices.saturating_sub(1));
+    let mut in_tree = vec![false; vertices];
+    // the lightest known edge connecting the vertex to the tree
+    let mut lightest: Vec<Option<(usize, Priority)>> = vec![None; vertices];
+    let mut frontier = Frontier::<D>::new(vertices);
+
+    for root in 0..vertices {
+        if in_tree[root] {
+            continue;
+        }
+        frontier.push(root, 0);
+
+        while let Some(Node { value: vertex, .. }) = frontier.pop() {
+            in_tree[vertex] = true;
+            if let Some((from, weight)) = lightest[vertex] {
+                tree.push(Edge {
+                    from,
+                    to: vertex,
+                    weight,
+                });
+            }
+
+            for (next, weight) in graph.edges(vertex) {
+                if in_tree[next] || lightest[next].is_some_and(|(_, known)| known <= weight) {
+                    continue;
+                }
+
+                lightest[next] = Some((vertex, weight));
+                frontier.push(next, weight);
+            }
+        }
+    }
+
+    tree
+}
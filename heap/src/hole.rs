@@ -0,0 +1,73 @@
+///
+/// hole in a slice used by the sift routines
+///
+/// the element at the hole position is taken out once, the other elements
+/// are moved into the hole one by one and the taken element is written back
+/// when the hole is dropped, so no element is cloned on the way
+///
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use crate::Index;
+
+pub(crate) struct Hole<'a, E> {
+    data: &'a mut [E],
+    element: ManuallyDrop<E>,
+    pos: Index,
+}
+
+impl<'a, E> Hole<'a, E> {
+    /// take the element at pos out of data, panics if pos is out of bounds
+    pub(crate) fn new(data: &'a mut [E], pos: Index) -> Self {
+        assert!(pos < data.len());
+        // SAFETY: pos is in bounds, the slot is written back on drop
+        let element = unsafe { ptr::read(&data[pos]) };
+        Self {
+            data,
+            element: ManuallyDrop::new(element),
+            pos,
+        }
+    }
+
+    /// current position of the hole
+    pub(crate) fn pos(&self) -> Index {
+        self.pos
+    }
+
+    /// the element taken out of the slice
+    pub(crate) fn element(&self) -> &E {
+        &self.element
+    }
+
+    /// element at index, index must differ from the hole position
+    pub(crate) fn get(&self, index: Index) -> &E {
+        debug_assert!(index != self.pos);
+        &self.data[index]
+    }
+
+    /// the whole slice, the hole position keeps a stale copy of the element
+    pub(crate) fn data(&self) -> &[E] {
+        self.data
+    }
+
+    /// move element at index into the hole, the hole goes to index
+    pub(crate) fn move_to(&mut self, index: Index) {
+        assert!(index != self.pos && index < self.data.len());
+        // SAFETY: both indexes are in bounds and differ
+        unsafe {
+            let base = self.data.as_mut_ptr();
+            ptr::copy_nonoverlapping(base.add(index), base.add(self.pos), 1);
+        }
+        self.pos = index;
+    }
+}
+
+impl<E> Drop for Hole<'_, E> {
+    fn drop(&mut self) {
+        // SAFETY: pos is in bounds and its content was moved away
+        unsafe {
+            let element = ManuallyDrop::take(&mut self.element);
+            ptr::write(&mut self.data[self.pos], element);
+        }
+    }
+}
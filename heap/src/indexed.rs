@@ -0,0 +1,234 @@
+///
+/// D-way heap with value -> index map
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:P) -> Option<P> | O(log N) |
+///   fn top() -> T                               | O(log N) |
+///   fn update(value:T, priority:P)              | O(log N) |
+///   fn remove(value:T) -> Node<T, P>            | O(log N) |
+///   fn contains(value:&T) -> bool               | O(1)     |
+///   fn priority(value:&T) -> Option<&P>         | O(1)     |
+///
+/// the values are kept once, in the heap array; the map keys them by
+/// their hashes to the ids of the nodes and the sift routines only move
+/// the index of every id, so they neither hash nor clone the values
+///
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{
+    bubble_up, bubble_up_index, push_down, ByPriority, Compare, HeapError, Index, MinOrder, Node,
+    Priority, Slot, TOP,
+};
+
+/// every value is kept at most once, the ids follow each move of the sift routines
+#[derive(Debug)]
+pub struct IndexedHeap<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    T: Hash + Eq,
+{
+    data: Vec<Slot<T, P>>,
+    /// current index of the node of every id
+    entries: Vec<Index>,
+    free: Vec<usize>,
+    /// ids of the kept values by their hashes
+    ids: HashMap<u64, Vec<usize>>,
+    hasher: RandomState,
+    cmp: C,
+}
+
+/// move hook keeping the index of every id up to date
+fn track<T, P>(entries: &mut [Index]) -> impl FnMut(&Slot<T, P>, Index) + '_ {
+    |slot, index| entries[slot.id] = index
+}
+
+impl<T, const D: Index, C, P> IndexedHeap<T, D, C, P>
+where
+    T: Hash + Eq,
+    C: Compare<P>,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            data: Vec::new(),
+            entries: Vec::new(),
+            free: Vec::new(),
+            ids: HashMap::new(),
+            hasher: RandomState::new(),
+            cmp,
+        }
+    }
+
+    /// create new heap with capacity
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        C: Default,
+    {
+        Self {
+            data: Vec::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            ids: HashMap::with_capacity(capacity),
+            hasher: RandomState::new(),
+            cmp: C::default(),
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// check is value kept in the heap
+    pub fn contains(&self, value: &T) -> bool {
+        self.index_of(value).is_some()
+    }
+
+    /// priority of the value or None if not kept
+    pub fn priority(&self, value: &T) -> Option<&P> {
+        self.index_of(value)
+            .map(|index| &self.data[index].node.priority)
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.data.first().map(|slot| &slot.node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(self.remove_at(TOP).value)
+    }
+
+    /// insert value or update priority of the kept one,
+    /// returns the replaced priority
+    pub fn insert(&mut self, value: T, priority: P) -> Option<P> {
+        if let Some(index) = self.index_of(&value) {
+            let old = std::mem::replace(&mut self.data[index].node.priority, priority);
+            self.sift(index);
+            return Some(old);
+        }
+
+        let index = self.data.len();
+        let id = self.free.pop().unwrap_or_else(|| {
+            self.entries.push(index);
+            self.entries.len() - 1
+        });
+        self.entries[id] = index;
+        self.ids
+            .entry(self.hasher.hash_one(&value))
+            .or_default()
+            .push(id);
+
+        self.data.push(Slot {
+            node: Node { value, priority },
+            id,
+        });
+        let cmp = ByPriority(&self.cmp);
+        bubble_up::<_, _, D>(&mut self.data, &cmp, &mut track(&mut self.entries));
+
+        None
+    }
+
+    /// remove the node keeping value, returns the removed node
+    pub fn remove(&mut self, value: T) -> Result<Node<T, P>, HeapError> {
+        if self.data.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let index = self.index_of(&value).ok_or(HeapError::NotFound)?;
+        Ok(self.remove_at(index))
+    }
+
+    /// change priority of the node keeping value
+    pub fn update(&mut self, value: T, priority: P) -> Result<(), HeapError> {
+        if self.data.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let index = self.index_of(&value).ok_or(HeapError::NotFound)?;
+        self.data[index].node.priority = priority;
+        self.sift(index);
+
+        Ok(())
+    }
+
+    /// index of the node keeping value
+    fn index_of(&self, value: &T) -> Option<Index> {
+        self.ids
+            .get(&self.hasher.hash_one(value))?
+            .iter()
+            .map(|&id| self.entries[id])
+            .find(|&index| self.data[index].node.value == *value)
+    }
+
+    fn remove_at(&mut self, index: Index) -> Node<T, P> {
+        let slot = self.data.swap_remove(index);
+        let hash = self.hasher.hash_one(&slot.node.value);
+        if let Some(ids) = self.ids.get_mut(&hash) {
+            ids.retain(|&id| id != slot.id);
+            if ids.is_empty() {
+                self.ids.remove(&hash);
+            }
+        }
+        self.free.push(slot.id);
+
+        // the last node moved into the gap may go either way
+        if index < self.data.len() {
+            self.entries[self.data[index].id] = index;
+            self.sift(index);
+        }
+
+        slot.node
+    }
+
+    fn sift(&mut self, index: Index) {
+        let cmp = ByPriority(&self.cmp);
+        let mut moved = track(&mut self.entries);
+        if bubble_up_index::<_, _, D>(&mut self.data, index, &cmp, &mut moved) == index {
+            push_down::<_, _, D>(&mut self.data, index, &cmp, &mut moved);
+        }
+    }
+} // IndexedHeap
+
+impl<T, const D: Index, C, P> Default for IndexedHeap<T, D, C, P>
+where
+    T: Hash + Eq,
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
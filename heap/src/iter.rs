@@ -0,0 +1,209 @@
+///
+/// iterators of the heap
+///
+///   fn iter() -> Iter                  | arbitrary order  |
+///   fn drain() -> Drain                | arbitrary order  |
+///   fn drain_sorted() -> DrainSorted   | top first        |
+///   fn into_sorted_vec() -> Vec<Node>  | top first        |
+///   fn into_iter() -> IntoIter         | arbitrary order  |
+///
+use std::iter::FusedIterator;
+
+use crate::{push_down, untracked, ByPriority, Compare, Heap, Index, Node, Slot, TOP};
+
+/// nodes by reference in arbitrary order
+#[derive(Debug)]
+pub struct Iter<'a, T, P> {
+    slots: std::slice::Iter<'a, Slot<T, P>>,
+}
+
+/// nodes removed from the heap in arbitrary order
+#[derive(Debug)]
+pub struct Drain<'a, T, P> {
+    slots: std::vec::Drain<'a, Slot<T, P>>,
+}
+
+/// nodes removed from the heap in priority order,
+/// nodes not yielded are removed when dropped
+#[derive(Debug)]
+pub struct DrainSorted<'a, T, const D: Index, C, P>
+where
+    C: Compare<P>,
+{
+    heap: &'a mut Heap<T, D, C, P>,
+}
+
+/// nodes of the consumed heap in arbitrary order
+#[derive(Debug)]
+pub struct IntoIter<T, P> {
+    slots: std::vec::IntoIter<Slot<T, P>>,
+}
+
+impl<T, const D: Index, C, P> Heap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// iterate nodes in arbitrary order
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter {
+            slots: self.data.iter(),
+        }
+    }
+
+    /// remove all nodes, yielded in arbitrary order
+    pub fn drain(&mut self) -> Drain<'_, T, P> {
+        for id in self.data.iter().map(|slot| slot.id).collect::<Vec<_>>() {
+            self.release(id);
+        }
+
+        Drain {
+            slots: self.data.drain(..),
+        }
+    }
+
+    /// remove all nodes, yielded in priority order
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, D, C, P> {
+        DrainSorted { heap: self }
+    }
+
+    /// consume the heap, nodes sorted in priority order (top first)
+    pub fn into_sorted_vec(self) -> Vec<Node<T, P>> {
+        let mut data = self.data;
+        let cmp = ByPriority(&self.cmp);
+
+        for end in (1..data.len()).rev() {
+            data.swap(TOP, end);
+            push_down::<_, _, D>(&mut data[..end], TOP, &cmp, &mut untracked);
+        }
+
+        data.into_iter().rev().map(|slot| slot.node).collect()
+    }
+}
+
+impl<'a, T, P> Iterator for Iter<'a, T, P> {
+    type Item = &'a Node<T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.next().map(|slot| &slot.node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T, P> ExactSizeIterator for Iter<'_, T, P> {}
+impl<T, P> FusedIterator for Iter<'_, T, P> {}
+
+impl<T, P> Iterator for Drain<'_, T, P> {
+    type Item = Node<T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.next().map(|slot| slot.node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T, P> ExactSizeIterator for Drain<'_, T, P> {}
+impl<T, P> FusedIterator for Drain<'_, T, P> {}
+
+impl<T, const D: Index, C, P> Iterator for DrainSorted<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    type Item = Node<T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        Some(self.heap.remove_at(TOP))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, const D: Index, C, P> ExactSizeIterator for DrainSorted<'_, T, D, C, P> where C: Compare<P> {}
+impl<T, const D: Index, C, P> FusedIterator for DrainSorted<'_, T, D, C, P> where C: Compare<P> {}
+
+impl<T, const D: Index, C, P> Drop for DrainSorted<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T, P> Iterator for IntoIter<T, P> {
+    type Item = Node<T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.next().map(|slot| slot.node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T, P> ExactSizeIterator for IntoIter<T, P> {}
+impl<T, P> FusedIterator for IntoIter<T, P> {}
+
+impl<T, const D: Index, C, P> IntoIterator for Heap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    type Item = Node<T, P>;
+    type IntoIter = IntoIter<T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            slots: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, const D: Index, C, P> IntoIterator for &'a Heap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    type Item = &'a Node<T, P>;
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const D: Index, C, P> FromIterator<(T, P)> for Heap<T, D, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (T, P)>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T, const D: Index, C, P> Extend<(T, P)> for Heap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// appends all nodes, bigger batches restore the order with one heapify pass
+    fn extend<I: IntoIterator<Item = (T, P)>>(&mut self, iter: I) {
+        let start = self.len();
+        self.append_nodes(
+            iter.into_iter()
+                .map(|(value, priority)| Node { value, priority }),
+        );
+        self.restore_tail(start);
+    }
+}
@@ -1,67 +1,308 @@
 /// simple heap D-way
 ///
-pub type Data<T> = Vec<Node<T>>;
+pub type Data<T, P = Priority> = Vec<Node<T, P>>;
 pub type Index = usize;
 pub type Priority = u64;
 
 // static const D: Index = 2;
-const TOP: Index = 0;
+pub(crate) const TOP: Index = 0;
 const FIRST_CHILD_INDEX: Index = 1;
 
+mod arena;
+mod binary;
+mod binomial;
+mod bounded;
+mod bucket;
+mod channel;
+mod concurrent;
+mod fibonacci;
+pub mod graph;
+mod hole;
+mod indexed;
+mod iter;
+mod minmax;
+mod pairing;
+mod peek_mut;
+mod persistent;
+mod quantile;
+mod radix;
+mod scheduler;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sort;
+mod stable;
+mod wheel;
+
+pub use binary::Codec;
+pub use binomial::BinomialHeap;
+pub use bounded::BoundedHeap;
+pub use bucket::BucketQueue;
+pub use channel::{channel, channel_with_compare, Receiver, Sender};
+pub use concurrent::ConcurrentHeap;
+pub use fibonacci::FibonacciHeap;
+use hole::Hole;
+pub use iter::{Drain, DrainSorted, IntoIter, Iter};
+pub use minmax::MinMaxHeap;
+pub use pairing::PairingHeap;
+pub use peek_mut::PeekMut;
+pub use persistent::{Leftist, LeftistHeap, PersistentHeap, Shape, Skew, SkewHeap};
+pub use quantile::{RunningMedian, RunningQuantile};
+pub use radix::RadixHeap;
+pub use scheduler::{Clock, Expired, MockClock, Scheduler, SystemClock};
+pub use sort::{heap_sort, kmerge, KMerge};
+pub use stable::StableHeap;
+pub use wheel::TimingWheel;
+
+pub use indexed::IndexedHeap;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// errors reported by heap operations which would otherwise panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapError {
+    /// operation requires at least one element
+    Empty,
+    /// searched value is not stored in the heap
+    NotFound,
+    /// handle does not refer to a node kept by the heap
+    InvalidHandle,
+    /// priority lower than the last removed one, kept by monotone heaps
+    BelowLast,
+    /// priority above the highest one accepted by the heap
+    OutOfRange,
+}
+
+impl std::fmt::Display for HeapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeapError::Empty => write!(f, "empty heap"),
+            HeapError::NotFound => write!(f, "value not found in heap"),
+            HeapError::InvalidHandle => write!(f, "invalid heap handle"),
+            HeapError::BelowLast => write!(f, "priority below the last removed priority"),
+            HeapError::OutOfRange => write!(f, "priority above max priority"),
+        }
+    }
+}
+
+impl std::error::Error for HeapError {}
+
+/// order of priorities kept by the heap
+///
+/// any `Fn(&P, &P) -> bool` closure can be used as well
+pub trait Compare<P> {
+    /// true when `a` has to be closer to the top than `b`
+    fn higher(&self, a: &P, b: &P) -> bool;
+}
+
+/// the lowest priority on the top (default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinOrder;
+
+/// the highest priority on the top
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxOrder;
+
+impl<P: Ord> Compare<P> for MinOrder {
+    fn higher(&self, a: &P, b: &P) -> bool {
+        a < b
+    }
+}
+
+impl<P: Ord> Compare<P> for MaxOrder {
+    fn higher(&self, a: &P, b: &P) -> bool {
+        a > b
+    }
+}
+
+/// the order of the inner comparison turned upside down
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reversed<C>(pub C);
+
+impl<P, C: Compare<P>> Compare<P> for Reversed<C> {
+    fn higher(&self, a: &P, b: &P) -> bool {
+        self.0.higher(b, a)
+    }
+}
+
+impl<P, F> Compare<P> for F
+where
+    F: Fn(&P, &P) -> bool,
+{
+    fn higher(&self, a: &P, b: &P) -> bool {
+        self(a, b)
+    }
+}
+
+/// f64 priority with total order (`f64::total_cmp`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrdF64(pub f64);
+
+impl PartialEq for OrdF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for OrdF64 {}
+
+impl PartialOrd for OrdF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdF64 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl From<f64> for OrdF64 {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct Node<T> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node<T, P = Priority> {
     pub value: T,
-    pub priority: Priority,
+    pub priority: P,
 }
 
-impl<T> PartialEq for Node<T>
+impl<T, P> PartialEq for Node<T, P>
 where
     T: PartialEq,
+    P: PartialEq,
 {
-    fn eq(&self, other: &Node<T>) -> bool {
+    fn eq(&self, other: &Node<T, P>) -> bool {
         self.value == other.value && self.priority == other.priority
     }
 }
 
+/// opaque reference to a node returned by `Heap::insert`,
+/// stays valid while the node is kept by the heap, other heaps reject it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    /// tag of the heap which issued the handle
+    owner: usize,
+    id: usize,
+    generation: usize,
+}
+
+/// tag of the next heap issuing handles, 0 is never issued
+static NEXT_OWNER: AtomicUsize = AtomicUsize::new(1);
+
+/// new owner tag for the handles of a heap
+pub(crate) fn next_owner() -> usize {
+    NEXT_OWNER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// node with the id of its handle
+#[derive(Debug, Clone)]
+pub(crate) struct Slot<T, P> {
+    pub(crate) node: Node<T, P>,
+    pub(crate) id: usize,
+}
+
+/// current index of the node behind a handle id
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    index: Option<Index>,
+    generation: usize,
+}
+
+/// compare heap elements by their priorities
+pub(crate) struct ByPriority<'a, C>(pub(crate) &'a C);
+
+impl<T, P, C> Compare<Node<T, P>> for ByPriority<'_, C>
+where
+    C: Compare<P>,
+{
+    fn higher(&self, a: &Node<T, P>, b: &Node<T, P>) -> bool {
+        self.0.higher(&a.priority, &b.priority)
+    }
+}
+
+impl<T, P, C> Compare<Slot<T, P>> for ByPriority<'_, C>
+where
+    C: Compare<P>,
+{
+    fn higher(&self, a: &Slot<T, P>, b: &Slot<T, P>) -> bool {
+        self.0.higher(&a.node.priority, &b.node.priority)
+    }
+}
+
 #[derive(Debug)]
-pub struct Heap<T, const D: Index = 2> {
-    data: Data<T>,
+pub struct Heap<T, const D: Index = 2, C = MinOrder, P = Priority> {
+    data: Vec<Slot<T, P>>,
+    entries: Vec<Entry>,
+    free: Vec<usize>,
+    /// tag of the handles issued by the heap
+    owner: usize,
+    cmp: C,
 }
 
-impl<T, const D: Index> Heap<T, D>
+impl<T, const D: Index, C, P> Heap<T, D, C, P>
 where
-    T: Clone + PartialEq + std::fmt::Debug,
+    C: Compare<P>,
 {
     /// create new heap
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
         Self {
-            data: Vec::<Node<T>>::new(),
+            data: Vec::<Slot<T, P>>::new(),
+            entries: Vec::new(),
+            free: Vec::new(),
+            owner: next_owner(),
+            cmp,
         }
     }
 
     /// create new heap from vector
-    pub fn new_from(mut data: Data<T>) -> Self {
-        heapify::<T, D>(&mut data);
-        Self { data }
+    pub fn new_from(data: Data<T, P>) -> Self
+    where
+        C: Default,
+    {
+        Self::new().from(data)
     }
 
     /// create new heap with capacity
-    pub fn with_capacity(capacity: usize) -> Self {
-        let data = Vec::<Node<T>>::with_capacity(capacity);
-        Self { data }
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        C: Default,
+    {
+        let mut heap = Self::new();
+        heap.data.reserve(capacity);
+        heap.entries.reserve(capacity);
+        heap
     }
 
     /// init from vector
-    pub fn from(mut self, data: Data<T>) -> Self {
-        self.data = data;
-        heapify::<T, D>(&mut self.data);
+    pub fn from(mut self, data: Data<T, P>) -> Self {
+        self.new_heap(data);
         self
     }
 
-    /// set new heap from vector
-    pub fn new_heap(&mut self, data: Data<T>) {
-        self.data = data;
-        heapify::<T, D>(&mut self.data);
+    /// set new heap from vector, handles of the previous nodes become invalid
+    pub fn new_heap(&mut self, data: Data<T, P>) {
+        self.clear();
+        self.append_nodes(data);
+        self.rebuild();
+    }
+
+    /// remove all nodes, their handles become invalid
+    pub fn clear(&mut self) {
+        for slot in std::mem::take(&mut self.data) {
+            self.release(slot.id);
+        }
     }
 
     /// check is heap empty
@@ -74,163 +315,396 @@ where
         self.data.len()
     }
 
-    // consider return Option<T>
-    pub fn peek(&self) -> T {
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.data.first().map(|slot| &slot.node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
         if self.data.is_empty() {
-            panic!("empty heap")
+            return None;
         }
-        self.data[TOP].value.clone()
+
+        Some(self.remove_at(TOP).value)
     }
 
-    // consider return Option<T>
-    pub fn top(&mut self) -> T {
-        let v = self.peek();
-        self.data.swap_remove(TOP);
-        if !self.data.is_empty() {
-            push_down::<T, D>(&mut self.data, TOP);
+    /// insert value, the returned handle refers to the node until it leaves the heap
+    pub fn insert(&mut self, value: T, priority: P) -> Handle {
+        let id = self.acquire(self.data.len());
+        self.data.push(Slot {
+            node: Node { value, priority },
+            id,
+        });
+
+        let entries = &mut self.entries;
+        bubble_up::<_, _, D>(&mut self.data, &ByPriority(&self.cmp), &mut track(entries));
+
+        Handle {
+            owner: self.owner,
+            id,
+            generation: self.entries[id].generation,
         }
+    }
 
-        v
+    /// node behind the handle or None if it left the heap
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&Node<T, P>> {
+        self.index_of(handle).map(|index| &self.data[index].node)
     }
 
-    pub fn insert(&mut self, value: T, priority: Priority) {
-        self.data.push(Node { value, priority });
-        bubble_up::<T, D>(&mut self.data);
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T, P>, HeapError> {
+        let index = self.index_of(handle).ok_or(HeapError::InvalidHandle)?;
+        Ok(self.remove_at(index))
     }
 
-    pub fn remove(&mut self, value: T) {
-        match self.data.len() {
-            0 => return, // panic!?
-            1 => self.data.clear(),
-            _ => {
-                if let Some(index) = self.find(&value) {
-                    self.data.swap_remove(index);
-                    push_down::<T, D>(&mut self.data, index);
-                }
-            } // consider panic! otherwise
+    /// change priority of the node behind the handle
+    pub fn update_by_handle(&mut self, handle: Handle, priority: P) -> Result<(), HeapError> {
+        let index = self.index_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.update_at(index, priority);
+        Ok(())
+    }
+
+    /// move all nodes of other into the heap, other is left empty,
+    /// handles of other become invalid, handles of self stay valid
+    pub fn append(&mut self, other: &mut Self) {
+        let start = self.len();
+        self.append_nodes(other.drain());
+        self.restore_tail(start);
+    }
+
+    /// consume both heaps, handles of self stay valid
+    pub fn merge(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+
+    /// consume all heaps restoring the order once, handles of self stay valid
+    pub fn merge_many(mut self, heaps: impl IntoIterator<Item = Self>) -> Self {
+        let start = self.len();
+        for heap in heaps {
+            self.append_nodes(heap);
         }
+        self.restore_tail(start);
+        self
     }
 
-    pub fn update(&mut self, value: T, priority: Priority) {
-        if let Some(index) = self.find(&value) {
-            let old_priority = self.data[index].priority;
-            self.data[index].priority = priority;
+    fn index_of(&self, handle: Handle) -> Option<Index> {
+        if handle.owner != self.owner {
+            return None;
+        }
+
+        self.entries
+            .get(handle.id)
+            .filter(|entry| entry.generation == handle.generation)
+            .and_then(|entry| entry.index)
+    }
 
-            if old_priority > priority {
-                bubble_up_index::<T, D>(&mut self.data, index);
-            } else {
-                push_down::<T, D>(&mut self.data, index);
+    fn remove_at(&mut self, index: Index) -> Node<T, P> {
+        let slot = self.data.swap_remove(index);
+        self.release(slot.id);
+
+        // the last node moved into the gap may go either way
+        if index < self.data.len() {
+            let cmp = ByPriority(&self.cmp);
+            let mut moved = track(&mut self.entries);
+            if bubble_up_index::<_, _, D>(&mut self.data, index, &cmp, &mut moved) == index {
+                push_down::<_, _, D>(&mut self.data, index, &cmp, &mut moved);
             }
         }
+
+        slot.node
     }
 
-    fn find(&self, value: &T) -> Option<Index> {
-        self.data.iter().position(|node| node.value == *value)
+    fn update_at(&mut self, index: Index, priority: P) {
+        let raise = self.cmp.higher(&priority, &self.data[index].node.priority);
+        self.data[index].node.priority = priority;
+
+        let cmp = ByPriority(&self.cmp);
+        let mut moved = track(&mut self.entries);
+        if raise {
+            bubble_up_index::<_, _, D>(&mut self.data, index, &cmp, &mut moved);
+        } else {
+            push_down::<_, _, D>(&mut self.data, index, &cmp, &mut moved);
+        }
+    }
+
+    /// push nodes at the end without restoring the heap order
+    fn append_nodes(&mut self, nodes: impl IntoIterator<Item = Node<T, P>>) {
+        for node in nodes {
+            let id = self.acquire(self.data.len());
+            self.data.push(Slot { node, id });
+        }
+    }
+
+    /// heap keeping the nodes in their order, None when they break the heap order
+    pub(crate) fn from_heap_order(data: Data<T, P>, cmp: C) -> Option<Self> {
+        if !is_heap::<_, _, D>(&data, &ByPriority(&cmp)) {
+            return None;
+        }
+
+        let mut heap = Self::with_compare(cmp);
+        heap.append_nodes(data);
+        Some(heap)
+    }
+
+    /// restore the heap order of all nodes (heapify)
+    fn rebuild(&mut self) {
+        let cmp = ByPriority(&self.cmp);
+        build_heap::<_, _, D>(&mut self.data, &cmp, &mut track(&mut self.entries));
+    }
+
+    /// restore the heap order after nodes were pushed at start..,
+    /// a few nodes are sifted up, many of them trigger rebuild
+    fn restore_tail(&mut self, start: Index) {
+        let added = self.data.len() - start;
+        if start == 0 || 2 * self.data.len() < added * self.data.len().ilog2() as usize {
+            self.rebuild();
+            return;
+        }
+
+        let cmp = ByPriority(&self.cmp);
+        let mut moved = track(&mut self.entries);
+        for index in start..self.data.len() {
+            bubble_up_index::<_, _, D>(&mut self.data, index, &cmp, &mut moved);
+        }
+    }
+
+    /// take a free handle id for the node at index
+    fn acquire(&mut self, index: Index) -> usize {
+        let id = self.free.pop().unwrap_or_else(|| {
+            self.entries.push(Entry::default());
+            self.entries.len() - 1
+        });
+
+        self.entries[id].index = Some(index);
+        id
+    }
+
+    /// invalidate handles using the id
+    fn release(&mut self, id: usize) {
+        let entry = &mut self.entries[id];
+        entry.index = None;
+        entry.generation += 1;
+        self.free.push(id);
     }
 } // Heap
 
-fn parent_index<const D: Index>(index: Index) -> Index {
+/// operations searching nodes by value, O(N)
+impl<T, const D: Index, C, P> Heap<T, D, C, P>
+where
+    T: PartialEq,
+    C: Compare<P>,
+{
+    /// remove the node keeping value, returns the removed node
+    pub fn remove(&mut self, value: T) -> Result<Node<T, P>, HeapError> {
+        if self.data.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let index = self.find(&value).ok_or(HeapError::NotFound)?;
+        Ok(self.remove_at(index))
+    }
+
+    /// change priority of the node keeping value
+    pub fn update(&mut self, value: T, priority: P) -> Result<(), HeapError> {
+        if self.data.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let index = self.find(&value).ok_or(HeapError::NotFound)?;
+        self.update_at(index, priority);
+        Ok(())
+    }
+
+    fn find(&self, value: &T) -> Option<Index> {
+        self.data.iter().position(|slot| slot.node.value == *value)
+    }
+}
+
+impl<T, const D: Index, C, P> Default for Heap<T, D, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// move hook keeping handle entries up to date
+fn track<T, P>(entries: &mut [Entry]) -> impl FnMut(&Slot<T, P>, Index) + '_ {
+    |slot, index| entries[slot.id].index = Some(index)
+}
+
+pub(crate) fn parent_index<const D: Index>(index: Index) -> Index {
     assert!(index > 0);
     (index - 1) / D
 }
 
-fn child_index<const D: Index>(index: Index, num: Index) -> Index {
+pub(crate) fn child_index<const D: Index>(index: Index, num: Index) -> Index {
     assert!(num > 0 && num <= D);
     D * index + num
 }
 
-fn first_child_index<const D: Index>(index: Index) -> Index {
+pub(crate) fn first_child_index<const D: Index>(index: Index) -> Index {
     child_index::<D>(index, FIRST_CHILD_INDEX)
 }
 
-fn bubble_up_index<T, const D: Index>(data: &mut [Node<T>], index: Index)
+/// move hook for heaps which do not track positions
+pub(crate) fn untracked<E>(_: &E, _: Index) {}
+
+/// sift element at index towards the top, returns its final index,
+/// `moved` is called for every element written to a new index
+pub(crate) fn bubble_up_index<E, C, const D: Index>(
+    data: &mut [E],
+    index: Index,
+    cmp: &C,
+    moved: &mut impl FnMut(&E, Index),
+) -> Index
 where
-    T: Clone + std::fmt::Debug,
+    C: Compare<E>,
 {
-    assert!(data.len() > index);
-
-    let current_item = data[index].clone();
-    let mut index = index;
-
-    let cmp = |a, b| a > b;
-
-    while index > 0 {
-        let parent_id = parent_index::<D>(index);
-        if cmp(data[parent_id].priority, current_item.priority) {
-            data[index] = data[parent_id].clone();
-            index = parent_id;
+    let mut hole = Hole::new(data, index);
+
+    while hole.pos() > 0 {
+        let parent_id = parent_index::<D>(hole.pos());
+        if cmp.higher(hole.element(), hole.get(parent_id)) {
+            let index = hole.pos();
+            hole.move_to(parent_id);
+            moved(hole.get(index), index);
         } else {
             break;
         }
     }
 
-    data[index] = current_item;
+    let index = hole.pos();
+    drop(hole);
+    moved(&data[index], index);
+    index
 }
 
-fn bubble_up<T, const D: Index>(data: &mut [Node<T>])
+pub(crate) fn bubble_up<E, C, const D: Index>(
+    data: &mut [E],
+    cmp: &C,
+    moved: &mut impl FnMut(&E, Index),
+) -> Index
 where
-    T: Clone + std::fmt::Debug,
+    C: Compare<E>,
 {
-    bubble_up_index::<T, D>(data, data.len() - 1);
+    bubble_up_index::<E, C, D>(data, data.len() - 1, cmp, moved)
 }
 
-fn highest_priority_child<T, const D: Index>(
-    data: &[Node<T>],
+fn highest_priority_child<'a, E, C, const D: Index>(
+    data: &'a [E],
     index: Index,
-) -> Option<(Index, Node<T>)>
+    cmp: &C,
+) -> Option<(Index, &'a E)>
 where
-    T: Clone + std::fmt::Debug,
+    C: Compare<E>,
 {
     use std::cmp::min;
     let mut child_id = first_child_index::<D>(index);
 
-    let cmp = |a, b| a > b; // TODO: pass as param
-
     if child_id < data.len() {
         let child_index_max = min(child_id + D, data.len());
-        let mut child_prio = data[child_id].priority;
 
-        for id in child_id + 1..child_index_max {
-            if !cmp(data[id].priority, child_prio) {
+        for (id, item) in data
+            .iter()
+            .enumerate()
+            .take(child_index_max)
+            .skip(child_id + 1)
+        {
+            if !cmp.higher(&data[child_id], item) {
                 child_id = id;
-                child_prio = data[id].priority;
             }
         }
 
-        Some((child_id, data[child_id].clone()))
+        Some((child_id, &data[child_id]))
     } else {
         None
     }
 }
 
-fn push_down<T, const D: Index>(data: &mut [Node<T>], index: Index)
+/// sift element at index towards the leaves, returns its final index,
+/// `moved` is called for every element written to a new index
+pub(crate) fn push_down<E, C, const D: Index>(
+    data: &mut [E],
+    index: Index,
+    cmp: &C,
+    moved: &mut impl FnMut(&E, Index),
+) -> Index
 where
-    T: Clone + std::fmt::Debug,
+    C: Compare<E>,
 {
-    let mut index = index;
-    let current_item = data[index].clone();
-
-    let cmp = |a, b| a > b; // TODO pass a param
-
-    while let Some((child_id, child_item)) = highest_priority_child::<T, D>(data, index) {
-        if cmp(current_item.priority, child_item.priority) {
-            data[index] = child_item;
-            index = child_id;
+    let mut hole = Hole::new(data, index);
+
+    while let Some((child_id, child_item)) =
+        highest_priority_child::<E, C, D>(hole.data(), hole.pos(), cmp)
+    {
+        if cmp.higher(child_item, hole.element()) {
+            let index = hole.pos();
+            hole.move_to(child_id);
+            moved(hole.get(index), index);
         } else {
             break;
         }
     }
 
-    data[index] = current_item;
+    let index = hole.pos();
+    drop(hole);
+    moved(&data[index], index);
+    index
 }
 
-pub fn heapify<T, const D: Index>(data: &mut [Node<T>])
-where
-    T: Clone + std::fmt::Debug,
+/// restore the heap order of the whole slice, pushing down from the last parent
+pub(crate) fn build_heap<E, C, const D: Index>(
+    data: &mut [E],
+    cmp: &C,
+    moved: &mut impl FnMut(&E, Index),
+) where
+    C: Compare<E>,
 {
-    for i in (0..data.len() / D + 1).rev() {
-        push_down::<T, D>(data, i);
+    if data.len() < 2 {
+        return;
     }
+
+    for i in (0..=parent_index::<D>(data.len() - 1)).rev() {
+        push_down::<_, _, D>(data, i, cmp, moved);
+    }
+}
+
+/// check the heap order of the whole slice, every element is not higher than its parent
+pub(crate) fn is_heap<E, C, const D: Index>(data: &[E], cmp: &C) -> bool
+where
+    C: Compare<E>,
+{
+    (1..data.len()).all(|i| !cmp.higher(&data[i], &data[parent_index::<D>(i)]))
+}
+
+/// build min heap in place
+pub fn heapify<T, const D: Index>(data: &mut [Node<T>]) {
+    heapify_by::<T, Priority, MinOrder, D>(data, &MinOrder);
+}
+
+/// build heap in place ordered by cmp
+pub fn heapify_by<T, P, C, const D: Index>(data: &mut [Node<T, P>], cmp: &C)
+where
+    C: Compare<P>,
+{
+    build_heap::<_, _, D>(data, &ByPriority(cmp), &mut untracked);
 }
 
 #[cfg(test)]
@@ -394,7 +868,7 @@ mod prv_test {
 
         let last = data.len() - 1;
 
-        bubble_up_index::<Item, D>(&mut data, last);
+        bubble_up_index::<_, _, D>(&mut data, last, &ByPriority(&MinOrder), &mut untracked);
 
         assert_eq!(data.len(), expected.len());
 
@@ -440,29 +914,31 @@ mod prv_test {
             },
         ];
 
-        if let Some((index, node)) = highest_priority_child::<Item, D>(&data, 0) {
+        if let Some((index, node)) =
+            highest_priority_child::<_, _, D>(&data, 0, &ByPriority(&MinOrder))
+        {
             assert_eq!(index, 2);
             assert_eq!(node.value, 1);
             assert_eq!(node.priority, 1);
             assert_eq!(data[index].value, 1);
             assert_eq!(data[index].priority, 1);
         } else {
-            assert!(false);
+            panic!("expected child");
         }
 
-        if let Some((index, node)) = highest_priority_child::<Item, D>(&data, 1) {
+        if let Some((index, node)) =
+            highest_priority_child::<_, _, D>(&data, 1, &ByPriority(&MinOrder))
+        {
             assert_eq!(index, 3);
             assert_eq!(node.value, 4);
             assert_eq!(node.priority, 4);
             assert_eq!(data[index].value, 4);
             assert_eq!(data[index].priority, 4);
         } else {
-            assert!(false);
+            panic!("expected child");
         }
 
-        if let Some((_, _)) = highest_priority_child::<Item, D>(&data, 5) {
-            assert!(false);
-        }
+        assert!(highest_priority_child::<_, _, D>(&data, 5, &ByPriority(&MinOrder)).is_none());
     }
 
     #[test]
@@ -500,14 +976,14 @@ mod prv_test {
                 },
             ];
 
-            push_down::<Item, D>(&mut data, 0);
+            push_down::<_, _, D>(&mut data, 0, &ByPriority(&MinOrder), &mut untracked);
 
             assert_eq!(data[0].value, 6);
             assert_eq!(data[0].priority, 0);
 
             data.swap_remove(0);
 
-            push_down::<Item, D>(&mut data, 0);
+            push_down::<_, _, D>(&mut data, 0, &ByPriority(&MinOrder), &mut untracked);
 
             assert_eq!(data[0].value, 1);
             assert_eq!(data[0].priority, 1);
@@ -543,14 +1019,14 @@ mod prv_test {
                 },
             ];
 
-            push_down::<Item, D>(&mut data, 0);
+            push_down::<_, _, D>(&mut data, 0, &ByPriority(&MinOrder), &mut untracked);
 
             assert_eq!(data[0].value, String::from("6"));
             assert_eq!(data[0].priority, 0);
 
             data.swap_remove(0);
 
-            push_down::<Item, D>(&mut data, 0);
+            push_down::<_, _, D>(&mut data, 0, &ByPriority(&MinOrder), &mut untracked);
 
             assert_eq!(data[0].value, String::from("1"));
             assert_eq!(data[0].priority, 1);
@@ -0,0 +1,205 @@
+///
+/// min-max heap, double ended priority queue
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:P)   | O(log N) |
+///   fn peek_min() -> Option<&T>      | O(1)     |
+///   fn peek_max() -> Option<&T>      | O(1)     |
+///   fn pop_min() -> Option<T>        | O(log N) |
+///   fn pop_max() -> Option<T>        | O(log N) |
+///
+/// binary tree in a vector, nodes on even levels (the root on level 0)
+/// are the lowest of their subtrees, nodes on odd levels the highest
+///
+use crate::{child_index, first_child_index, parent_index, Data, Index, Node, Priority, TOP};
+
+const D: Index = 2;
+/// index of the first node with a grandparent
+const FIRST_GRANDCHILD: Index = 3;
+
+#[derive(Debug)]
+pub struct MinMaxHeap<T, P = Priority> {
+    data: Data<T, P>,
+}
+
+fn is_min_level(index: Index) -> bool {
+    (index + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T, P> MinMaxHeap<T, P>
+where
+    P: Ord,
+{
+    /// create new heap
+    pub fn new() -> Self {
+        Self {
+            data: Vec::<Node<T, P>>::new(),
+        }
+    }
+
+    /// create new heap with capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::<Node<T, P>>::with_capacity(capacity),
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn insert(&mut self, value: T, priority: P) {
+        self.data.push(Node { value, priority });
+        self.bubble_up(self.data.len() - 1);
+    }
+
+    /// reference to the value of the lowest priority
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first().map(|node| &node.value)
+    }
+
+    /// reference to the value of the highest priority
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.data[index].value)
+    }
+
+    /// remove and return the value of the lowest priority
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(self.remove_at(TOP))
+    }
+
+    /// remove and return the value of the highest priority
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+        Some(self.remove_at(index))
+    }
+
+    fn max_index(&self) -> Option<Index> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(TOP),
+            2 => Some(first_child_index::<D>(TOP)),
+            _ => {
+                let (left, right) = (child_index::<D>(TOP, 1), child_index::<D>(TOP, 2));
+                if self.data[left].priority >= self.data[right].priority {
+                    Some(left)
+                } else {
+                    Some(right)
+                }
+            }
+        }
+    }
+
+    fn remove_at(&mut self, index: Index) -> T {
+        let node = self.data.swap_remove(index);
+        if index < self.data.len() {
+            self.trickle_down(index);
+        }
+        node.value
+    }
+
+    /// is the node at a higher than the one at b, in the order of the level of a
+    fn higher(&self, a: Index, b: Index, min_level: bool) -> bool {
+        if min_level {
+            self.data[a].priority < self.data[b].priority
+        } else {
+            self.data[a].priority > self.data[b].priority
+        }
+    }
+
+    fn bubble_up(&mut self, index: Index) {
+        if index == TOP {
+            return;
+        }
+
+        let min_level = is_min_level(index);
+        let parent = parent_index::<D>(index);
+
+        // a node which does not fit its own level belongs to the levels of the parent
+        if self.higher(parent, index, min_level) {
+            self.data.swap(index, parent);
+            self.bubble_up_grandparents(parent, !min_level);
+        } else {
+            self.bubble_up_grandparents(index, min_level);
+        }
+    }
+
+    fn bubble_up_grandparents(&mut self, index: Index, min_level: bool) {
+        let mut index = index;
+        while index >= FIRST_GRANDCHILD {
+            let grandparent = parent_index::<D>(parent_index::<D>(index));
+            if self.higher(index, grandparent, min_level) {
+                self.data.swap(index, grandparent);
+                index = grandparent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn trickle_down(&mut self, index: Index) {
+        let min_level = is_min_level(index);
+        let mut index = index;
+
+        while let Some(next) = self.highest_descendant(index, min_level) {
+            if parent_index::<D>(next) == index {
+                // a child, the last level of the subtree
+                if self.higher(next, index, min_level) {
+                    self.data.swap(next, index);
+                }
+                return;
+            }
+
+            if !self.higher(next, index, min_level) {
+                return;
+            }
+
+            self.data.swap(next, index);
+            let parent = parent_index::<D>(next);
+            if self.higher(parent, next, min_level) {
+                self.data.swap(next, parent);
+            }
+            index = next;
+        }
+    }
+
+    /// the highest of children and grandchildren, in the order of the level
+    fn highest_descendant(&self, index: Index, min_level: bool) -> Option<Index> {
+        let first_child = first_child_index::<D>(index);
+        if first_child >= self.data.len() {
+            return None;
+        }
+
+        let children = first_child..(first_child + D).min(self.data.len());
+        let first_grandchild = first_child_index::<D>(first_child);
+        let grandchildren = first_grandchild..(first_grandchild + D * D).min(self.data.len());
+
+        children.chain(grandchildren).reduce(|best, next| {
+            if self.higher(next, best, min_level) {
+                next
+            } else {
+                best
+            }
+        })
+    }
+} // MinMaxHeap
+
+impl<T, P> Default for MinMaxHeap<T, P>
+where
+    P: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,289 @@
+///
+/// pairing heap
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:P) -> Handle     | O(1)               |
+///   fn meld(other:Self)                          | O(1)               |
+///   fn peek() -> T                               | O(1)               |
+///   fn top() -> T                                | O(log N) amortized |
+///   fn update_by_handle(handle, priority:P)      | O(log N) amortized |
+///   fn remove_by_handle(handle) -> Node<T, P>    | O(log N) amortized |
+///   fn update(value:T, priority:P)               | O(N)               |
+///   fn remove(value:T) -> Node<T, P>             | O(N)               |
+///
+/// nodes are kept in an arena and linked by ids, `meld` moves the arena
+/// groups instead of the nodes, O(1) for heaps not melded before and
+/// O(G) for G groups left by earlier melds of the smaller side
+///
+use crate::arena::{Id, MeldArena};
+use crate::{Compare, Handle, HeapError, MinOrder, Node, Priority};
+
+#[derive(Debug)]
+struct PairNode<T, P> {
+    node: Node<T, P>,
+    /// the leftmost child
+    child: Option<Id>,
+    /// right sibling
+    next: Option<Id>,
+    /// left sibling or parent of the leftmost child
+    prev: Option<Id>,
+}
+
+#[derive(Debug)]
+pub struct PairingHeap<T, C = MinOrder, P = Priority> {
+    arena: MeldArena<PairNode<T, P>>,
+    root: Option<Id>,
+    cmp: C,
+}
+
+impl<T, C, P> PairingHeap<T, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            arena: MeldArena::new(),
+            root: None,
+            cmp,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.root.map(|root| &self.arena[root].node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        Some(self.remove_at(root).value)
+    }
+
+    /// insert value, the returned handle refers to the node until it leaves the heap
+    pub fn insert(&mut self, value: T, priority: P) -> Handle {
+        let id = self.arena.insert(PairNode {
+            node: Node { value, priority },
+            child: None,
+            next: None,
+            prev: None,
+        });
+        self.root = self.meld_roots(self.root, Some(id));
+        self.arena.handle(id)
+    }
+
+    /// node behind the handle or None if it left the heap
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&Node<T, P>> {
+        self.arena.id_of(handle).map(|id| &self.arena[id].node)
+    }
+
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T, P>, HeapError> {
+        let id = self.arena.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        Ok(self.remove_at(id))
+    }
+
+    /// change priority of the node behind the handle
+    pub fn update_by_handle(&mut self, handle: Handle, priority: P) -> Result<(), HeapError> {
+        let id = self.arena.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.update_at(id, priority);
+        Ok(())
+    }
+
+    /// move all nodes of other into the heap, handles of both heaps stay valid
+    pub fn meld(&mut self, other: Self) {
+        self.arena.meld(other.arena);
+        self.root = self.meld_roots(self.root, other.root);
+    }
+
+    fn update_at(&mut self, id: Id, priority: P) {
+        let raise = self.cmp.higher(&priority, &self.arena[id].node.priority);
+        self.arena[id].node.priority = priority;
+
+        if Some(id) == self.root {
+            if !raise {
+                let rest = self.take_children(id);
+                self.root = self.meld_roots(rest, Some(id));
+            }
+        } else {
+            self.cut(id);
+            let rest = if raise { None } else { self.take_children(id) };
+            let root = self.meld_roots(self.root, rest);
+            self.root = self.meld_roots(root, Some(id));
+        }
+    }
+
+    fn remove_at(&mut self, id: Id) -> Node<T, P> {
+        let rest = self.take_children(id);
+        if Some(id) == self.root {
+            self.root = rest;
+        } else {
+            self.cut(id);
+            self.root = self.meld_roots(self.root, rest);
+        }
+
+        self.arena.remove(id).node
+    }
+
+    /// link two roots, the lower one becomes the leftmost child of the other
+    fn link(&mut self, a: Id, b: Id) -> Id {
+        let (parent, child) = if self
+            .cmp
+            .higher(&self.arena[b].node.priority, &self.arena[a].node.priority)
+        {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let first = self.arena[parent].child;
+        if let Some(first) = first {
+            self.arena[first].prev = Some(child);
+        }
+
+        self.arena[child].next = first;
+        self.arena[child].prev = Some(parent);
+        self.arena[parent].child = Some(child);
+
+        parent
+    }
+
+    fn meld_roots(&mut self, a: Option<Id>, b: Option<Id>) -> Option<Id> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(self.link(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+
+    /// detach the node and its subtree from the parent
+    fn cut(&mut self, id: Id) {
+        let prev = self.arena[id].prev.take().expect("node is not a root");
+        let next = self.arena[id].next.take();
+
+        if self.arena[prev].child == Some(id) {
+            self.arena[prev].child = next;
+        } else {
+            self.arena[prev].next = next;
+        }
+
+        if let Some(next) = next {
+            self.arena[next].prev = Some(prev);
+        }
+    }
+
+    /// detach children of the node and pair them into one root
+    fn take_children(&mut self, id: Id) -> Option<Id> {
+        let mut child = self.arena[id].child.take();
+
+        // first pass: link pairs from the left
+        let mut pairs = Vec::new();
+        while let Some(a) = child {
+            let b = self.arena[a].next.take();
+            self.arena[a].prev = None;
+
+            match b {
+                Some(b) => {
+                    child = self.arena[b].next.take();
+                    self.arena[b].prev = None;
+                    pairs.push(self.link(a, b));
+                }
+                None => {
+                    child = None;
+                    pairs.push(a);
+                }
+            }
+        }
+
+        // second pass: link the pairs from the right
+        let mut root = pairs.pop();
+        while let Some(pair) = pairs.pop() {
+            root = self.meld_roots(Some(pair), root);
+        }
+
+        root
+    }
+} // PairingHeap
+
+/// operations searching nodes by value, O(N)
+impl<T, C, P> PairingHeap<T, C, P>
+where
+    T: PartialEq,
+    C: Compare<P>,
+{
+    /// remove the node keeping value, returns the removed node
+    pub fn remove(&mut self, value: T) -> Result<Node<T, P>, HeapError> {
+        if self.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let id = self.find(&value).ok_or(HeapError::NotFound)?;
+        Ok(self.remove_at(id))
+    }
+
+    /// change priority of the node keeping value
+    pub fn update(&mut self, value: T, priority: P) -> Result<(), HeapError> {
+        if self.is_empty() {
+            return Err(HeapError::Empty);
+        }
+
+        let id = self.find(&value).ok_or(HeapError::NotFound)?;
+        self.update_at(id, priority);
+        Ok(())
+    }
+
+    /// walk the tree from the root through children and right siblings
+    fn find(&self, value: &T) -> Option<Id> {
+        let mut pending: Vec<Id> = self.root.into_iter().collect();
+        while let Some(id) = pending.pop() {
+            let pair = &self.arena[id];
+            if pair.node.value == *value {
+                return Some(id);
+            }
+            pending.extend(pair.child);
+            pending.extend(pair.next);
+        }
+        None
+    }
+}
+
+impl<T, C, P> Default for PairingHeap<T, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
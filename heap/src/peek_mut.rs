@@ -0,0 +1,80 @@
+///
+/// mutable access to the top node of the heap
+///
+/// the heap order is restored when the guard is dropped,
+/// only if the node was accessed mutably
+///
+use std::ops::{Deref, DerefMut};
+
+use crate::{push_down, track, ByPriority, Compare, Heap, Index, Node, TOP};
+
+#[derive(Debug)]
+pub struct PeekMut<'a, T, const D: Index, C, P>
+where
+    C: Compare<P>,
+{
+    heap: &'a mut Heap<T, D, C, P>,
+    changed: bool,
+}
+
+impl<T, const D: Index, C, P> Heap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// guard of the top node or None for empty heap
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, D, C, P>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(PeekMut {
+            heap: self,
+            changed: false,
+        })
+    }
+}
+
+impl<T, const D: Index, C, P> PeekMut<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// remove the top node from the heap
+    pub fn pop(mut this: Self) -> Node<T, P> {
+        this.changed = false;
+        this.heap.remove_at(TOP)
+    }
+}
+
+impl<T, const D: Index, C, P> Deref for PeekMut<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    type Target = Node<T, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.data[TOP].node
+    }
+}
+
+impl<T, const D: Index, C, P> DerefMut for PeekMut<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.changed = true;
+        &mut self.heap.data[TOP].node
+    }
+}
+
+impl<T, const D: Index, C, P> Drop for PeekMut<'_, T, D, C, P>
+where
+    C: Compare<P>,
+{
+    fn drop(&mut self) {
+        if self.changed {
+            let heap = &mut *self.heap;
+            let cmp = ByPriority(&heap.cmp);
+            push_down::<_, _, D>(&mut heap.data, TOP, &cmp, &mut track(&mut heap.entries));
+        }
+    }
+}
@@ -0,0 +1,234 @@
+///
+/// persistent leftist and skew heaps
+///
+/// expected API and performance:
+///
+///   operation                                   | leftist  | skew               |
+///   fn insert(value:T, priority:P) -> Self      | O(log N) | O(log N) amortized |
+///   fn merge(other:&Self) -> Self               | O(log N) | O(log N) amortized |
+///   fn peek() -> T                              | O(1)     | O(1)               |
+///   fn top() -> (T, Self)                       | O(log N) | O(log N) amortized |
+///
+/// every operation returns a new version sharing the untouched subtrees
+/// with the old one (`Rc`), old versions stay valid and cloning is O(1),
+/// the amortized bounds of the skew heap do not hold when old versions are reused
+///
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::{Compare, MinOrder, Node, Priority};
+
+type Link<T, P> = Option<Rc<Tree<T, P>>>;
+
+#[derive(Debug)]
+struct Tree<T, P> {
+    node: Rc<Node<T, P>>,
+    /// length of the right spine, kept by the leftist heap
+    rank: usize,
+    left: Link<T, P>,
+    right: Link<T, P>,
+}
+
+/// how two trees are merged
+pub trait Shape {
+    /// swap the children on every merge instead of keeping the shorter right spine
+    const SKEW: bool;
+}
+
+/// keeps the shorter right spine, worst case bounds
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Leftist;
+
+/// swaps the children on every merge, amortized bounds
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Skew;
+
+impl Shape for Leftist {
+    const SKEW: bool = false;
+}
+
+impl Shape for Skew {
+    const SKEW: bool = true;
+}
+
+fn rank<T, P>(link: &Link<T, P>) -> usize {
+    link.as_ref().map_or(0, |tree| tree.rank)
+}
+
+/// new tree of the node with the left subtree and the merged right spine
+fn join<S: Shape, T, P>(node: Rc<Node<T, P>>, left: Link<T, P>, merged: Link<T, P>) -> Link<T, P> {
+    let (left, right) = if S::SKEW || rank(&left) < rank(&merged) {
+        (merged, left)
+    } else {
+        (left, merged)
+    };
+
+    Some(Rc::new(Tree {
+        node,
+        rank: rank(&right) + 1,
+        left,
+        right,
+    }))
+}
+
+#[derive(Debug)]
+pub struct PersistentHeap<T, S, C = MinOrder, P = Priority> {
+    root: Link<T, P>,
+    len: usize,
+    cmp: C,
+    shape: PhantomData<S>,
+}
+
+pub type LeftistHeap<T, C = MinOrder, P = Priority> = PersistentHeap<T, Leftist, C, P>;
+pub type SkewHeap<T, C = MinOrder, P = Priority> = PersistentHeap<T, Skew, C, P>;
+
+impl<T, S, C, P> PersistentHeap<T, S, C, P>
+where
+    S: Shape,
+    C: Compare<P> + Clone,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+            shape: PhantomData,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|tree| &tree.node.value)
+    }
+
+    /// return copy of the top value and the heap without it, panics on empty heap
+    pub fn top(&self) -> (T, Self)
+    where
+        T: Clone,
+    {
+        let (value, rest) = self.pop().expect("empty heap");
+        (value.clone(), rest)
+    }
+
+    /// return the top value and the heap without it or None for empty heap
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        let tree = self.root.as_ref()?;
+        let root = self.merge_links(tree.left.clone(), tree.right.clone());
+        Some((&tree.node.value, self.version(root, self.len - 1)))
+    }
+
+    /// new version with the value inserted
+    pub fn insert(&self, value: T, priority: P) -> Self {
+        let single = join::<S, T, P>(Rc::new(Node { value, priority }), None, None);
+        let root = self.merge_links(self.root.clone(), single);
+        self.version(root, self.len + 1)
+    }
+
+    /// new version with the nodes of both heaps
+    pub fn merge(&self, other: &Self) -> Self {
+        let root = self.merge_links(self.root.clone(), other.root.clone());
+        self.version(root, self.len + other.len)
+    }
+
+    fn version(&self, root: Link<T, P>, len: usize) -> Self {
+        Self {
+            root,
+            len,
+            cmp: self.cmp.clone(),
+            shape: PhantomData,
+        }
+    }
+
+    /// merge along the right spines, the path is rebuilt from the bottom
+    fn merge_links(&self, a: Link<T, P>, b: Link<T, P>) -> Link<T, P> {
+        let mut path = Vec::new();
+        let (mut a, mut b) = (a, b);
+
+        let mut merged = loop {
+            match (a, b) {
+                (Some(x), Some(y)) => {
+                    let (high, low) = if self.cmp.higher(&y.node.priority, &x.node.priority) {
+                        (y, x)
+                    } else {
+                        (x, y)
+                    };
+                    a = high.right.clone();
+                    b = Some(low);
+                    path.push(high);
+                }
+                (rest, None) | (None, rest) => break rest,
+            }
+        };
+
+        while let Some(high) = path.pop() {
+            merged = join::<S, T, P>(high.node.clone(), high.left.clone(), merged);
+        }
+
+        merged
+    }
+} // PersistentHeap
+
+impl<T, S, C, P> Clone for PersistentHeap<T, S, C, P>
+where
+    C: Clone,
+{
+    /// O(1), the versions share all nodes
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+            cmp: self.cmp.clone(),
+            shape: PhantomData,
+        }
+    }
+}
+
+impl<T, S, C, P> Default for PersistentHeap<T, S, C, P>
+where
+    S: Shape,
+    C: Compare<P> + Clone + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S, C, P> Drop for PersistentHeap<T, S, C, P> {
+    /// long spines would overflow the stack with the recursive drop
+    fn drop(&mut self) {
+        let mut stack: Vec<Rc<Tree<T, P>>> = self.root.take().into_iter().collect();
+        while let Some(tree) = stack.pop() {
+            if let Ok(mut tree) = Rc::try_unwrap(tree) {
+                stack.extend(tree.left.take());
+                stack.extend(tree.right.take());
+            }
+        }
+    }
+}
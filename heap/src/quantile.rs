@@ -0,0 +1,192 @@
+///
+/// running quantiles of a stream of samples kept in two heaps
+///
+/// expected API and performance:
+///
+///   fn insert(sample:P) -> Handle      | O(log N) |
+///   fn remove(handle) -> P             | O(log N) |
+///   fn quantile() -> Option<&P>        | O(1)     |
+///
+/// the samples up to the quantile are in a max ordered heap, the rest
+/// of them in a min ordered one, every insert and remove moves at most
+/// one sample between them, the quantile is the top of the lower heap
+///
+/// the quantile q is the sample of rank floor(q * (N - 1)) counted from 0,
+/// the median of an even count is the lower one of the middle samples;
+/// a sliding window removes the expired samples by their handles
+///
+use crate::arena::Arena;
+use crate::{Handle, Heap, HeapError, MaxOrder, MinOrder, PeekMut, Priority, TOP};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Low,
+    High,
+}
+
+/// position of a sample in the heaps
+#[derive(Debug)]
+struct Sample {
+    side: Side,
+    handle: Handle,
+}
+
+#[derive(Debug)]
+pub struct RunningQuantile<P = Priority> {
+    q: f64,
+    /// samples up to the quantile, the quantile on the top
+    low: Heap<usize, 2, MaxOrder, P>,
+    high: Heap<usize, 2, MinOrder, P>,
+    samples: Arena<Sample>,
+}
+
+impl<P> RunningQuantile<P>
+where
+    P: Ord,
+{
+    /// create new estimator of the quantile q, panics when q is out of 0..=1
+    pub fn new(q: f64) -> Self {
+        assert!((0.0..=1.0).contains(&q), "quantile out of 0..=1");
+        Self {
+            q,
+            low: Heap::new(),
+            high: Heap::new(),
+            samples: Arena::new(),
+        }
+    }
+
+    /// check is estimator empty
+    pub fn is_empty(&self) -> bool {
+        self.samples.len() == 0
+    }
+
+    /// number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// the estimated quantile
+    pub fn q(&self) -> f64 {
+        self.q
+    }
+
+    /// the quantile of the kept samples or None for no samples
+    pub fn quantile(&self) -> Option<&P> {
+        self.low.data.get(TOP).map(|slot| &slot.node.priority)
+    }
+
+    /// add sample, the returned handle refers to it until it is removed
+    pub fn insert(&mut self, sample: P) -> Handle {
+        let side = match self.quantile() {
+            Some(quantile) if sample > *quantile => Side::High,
+            _ => Side::Low,
+        };
+
+        let (low, high) = (&mut self.low, &mut self.high);
+        let id = self.samples.insert_with(|id| Sample {
+            side,
+            handle: match side {
+                Side::Low => low.insert(id, sample),
+                Side::High => high.insert(id, sample),
+            },
+        });
+        self.rebalance();
+        self.samples.handle(id)
+    }
+
+    /// remove the sample behind the handle
+    pub fn remove(&mut self, handle: Handle) -> Result<P, HeapError> {
+        let id = self.samples.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        let sample = self.samples.remove(id);
+        let node = match sample.side {
+            Side::Low => self.low.remove_by_handle(sample.handle),
+            Side::High => self.high.remove_by_handle(sample.handle),
+        }
+        .expect("sample kept by its heap");
+
+        self.rebalance();
+        Ok(node.priority)
+    }
+
+    /// number of samples the lower heap keeps, the quantile is the highest of them
+    fn low_len(&self) -> usize {
+        match self.len() {
+            0 => 0,
+            len => ((len - 1) as f64 * self.q).floor() as usize + 1,
+        }
+    }
+
+    /// move the top of one heap to the other until the lower one has low_len samples
+    fn rebalance(&mut self) {
+        let target = self.low_len();
+        while self.low.len() > target {
+            let node = PeekMut::pop(self.low.peek_mut().expect("low samples"));
+            self.place(node.value, Side::High, node.priority);
+        }
+        while self.low.len() < target {
+            let node = PeekMut::pop(self.high.peek_mut().expect("high samples"));
+            self.place(node.value, Side::Low, node.priority);
+        }
+    }
+
+    /// insert the sample to the heap of the side
+    fn place(&mut self, id: usize, side: Side, sample: P) {
+        let handle = match side {
+            Side::Low => self.low.insert(id, sample),
+            Side::High => self.high.insert(id, sample),
+        };
+        self.samples[id] = Sample { side, handle };
+    }
+} // RunningQuantile
+
+/// running median, the lower one of the middle samples for an even count
+#[derive(Debug)]
+pub struct RunningMedian<P = Priority> {
+    quantile: RunningQuantile<P>,
+}
+
+impl<P> RunningMedian<P>
+where
+    P: Ord,
+{
+    /// create new median estimator
+    pub fn new() -> Self {
+        Self {
+            quantile: RunningQuantile::new(0.5),
+        }
+    }
+
+    /// check is estimator empty
+    pub fn is_empty(&self) -> bool {
+        self.quantile.is_empty()
+    }
+
+    /// number of samples
+    pub fn len(&self) -> usize {
+        self.quantile.len()
+    }
+
+    /// the median of the kept samples or None for no samples
+    pub fn median(&self) -> Option<&P> {
+        self.quantile.quantile()
+    }
+
+    /// add sample, the returned handle refers to it until it is removed
+    pub fn insert(&mut self, sample: P) -> Handle {
+        self.quantile.insert(sample)
+    }
+
+    /// remove the sample behind the handle
+    pub fn remove(&mut self, handle: Handle) -> Result<P, HeapError> {
+        self.quantile.remove(handle)
+    }
+} // RunningMedian
+
+impl<P> Default for RunningMedian<P>
+where
+    P: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,130 @@
+///
+/// radix heap, monotone priority queue of `Priority` (u64) keys
+///
+/// expected API and performance:
+///
+///   fn insert(value:T, priority:Priority)   | O(1)                |
+///   fn peek() -> T                          | O(B)                |
+///   fn top() -> T                           | O(log C) amortized  |
+///
+/// the lowest priority is on the top, inserted priorities must not be below
+/// the last removed one, C is the span between inserted and removed priorities;
+/// peek is O(1) while nodes of the last removed priority are left, otherwise
+/// it scans the B nodes of the lowest non empty bucket
+///
+/// bucket `i` holds the priorities differing from the last removed one
+/// in the bit `i - 1` as the highest one, bucket 0 the equal ones,
+/// a pop redistributes the lowest non empty bucket into the lower ones
+///
+use crate::{HeapError, Node, Priority};
+
+const BUCKETS: usize = Priority::BITS as usize + 1;
+
+#[derive(Debug)]
+pub struct RadixHeap<T> {
+    buckets: Vec<Vec<Node<T>>>,
+    /// last removed priority
+    last: Priority,
+    len: usize,
+}
+
+impl<T> RadixHeap<T> {
+    /// create new heap
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| Vec::new()).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// last removed priority, the lowest priority accepted by insert
+    pub fn last(&self) -> Priority {
+        self.last
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_peek().expect("empty heap").clone()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        if let Some(node) = self.buckets[0].last() {
+            return Some(&node.value);
+        }
+
+        // the node pop takes after the redistribution, the last of the lowest
+        let bucket = self.buckets.iter().find(|bucket| !bucket.is_empty())?;
+        bucket
+            .iter()
+            .rev()
+            .min_by_key(|node| node.priority)
+            .map(|node| &node.value)
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.pop().expect("empty heap")
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        if self.buckets[0].is_empty() {
+            self.redistribute()?;
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop().map(|node| node.value)
+    }
+
+    /// insert value, fails when the priority is below the last removed one
+    pub fn insert(&mut self, value: T, priority: Priority) -> Result<(), HeapError> {
+        if priority < self.last {
+            return Err(HeapError::BelowLast);
+        }
+
+        let bucket = self.bucket_of(priority);
+        self.buckets[bucket].push(Node { value, priority });
+        self.len += 1;
+        Ok(())
+    }
+
+    fn bucket_of(&self, priority: Priority) -> usize {
+        (Priority::BITS - (priority ^ self.last).leading_zeros()) as usize
+    }
+
+    /// move the lowest non empty bucket into the lower ones,
+    /// its lowest priority becomes the last one and lands in bucket 0
+    fn redistribute(&mut self) -> Option<()> {
+        let index = self.buckets.iter().position(|bucket| !bucket.is_empty())?;
+        let nodes = std::mem::take(&mut self.buckets[index]);
+        self.last = nodes.iter().map(|node| node.priority).min()?;
+
+        for node in nodes {
+            let bucket = self.bucket_of(node.priority);
+            self.buckets[bucket].push(node);
+        }
+
+        Some(())
+    }
+} // RadixHeap
+
+impl<T> Default for RadixHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
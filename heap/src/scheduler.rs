@@ -0,0 +1,165 @@
+///
+/// deadline scheduler keeping (deadline, task) nodes in a `Heap`
+///
+/// expected API and performance:
+///
+///   fn schedule(deadline, task:T) -> Handle    | O(log N)              |
+///   fn cancel(handle) -> Node                  | O(log N)              |
+///   fn reschedule(handle, deadline)            | O(log N)              |
+///   fn next_deadline() -> Option<Priority>     | O(1)                  |
+///   fn expired(now) -> Expired                 | O(log N) per task     |
+///
+/// deadlines are `Priority` ticks of a `Clock`, the unit is up to the clock,
+/// `TimingWheel` is the alternative for very high timer counts
+///
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::{Handle, Heap, HeapError, Index, Node, PeekMut, Priority, TOP};
+
+/// source of the current time in ticks
+pub trait Clock {
+    fn now(&self) -> Priority;
+}
+
+/// milliseconds since the clock was created
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Priority {
+        self.start.elapsed().as_millis() as Priority
+    }
+}
+
+/// clock moved by hand, for tests
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: AtomicU64,
+}
+
+impl MockClock {
+    pub fn new(now: Priority) -> Self {
+        Self {
+            now: AtomicU64::new(now),
+        }
+    }
+
+    /// move the clock forward by ticks
+    pub fn advance(&self, ticks: Priority) {
+        self.now.fetch_add(ticks, Ordering::SeqCst);
+    }
+
+    /// set the current time
+    pub fn set(&self, now: Priority) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Priority {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug)]
+pub struct Scheduler<T, const D: Index = 2> {
+    heap: Heap<T, D>,
+}
+
+/// tasks removed from the scheduler in deadline order while their deadline
+/// is not after now, expired tasks not yielded stay scheduled
+#[derive(Debug)]
+pub struct Expired<'a, T, const D: Index> {
+    heap: &'a mut Heap<T, D>,
+    now: Priority,
+}
+
+impl<T, const D: Index> Scheduler<T, D> {
+    /// create new scheduler
+    pub fn new() -> Self {
+        Self { heap: Heap::new() }
+    }
+
+    /// check is scheduler empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// number of scheduled tasks
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// schedule task, the returned handle refers to it until it expires or is cancelled
+    pub fn schedule(&mut self, deadline: Priority, task: T) -> Handle {
+        self.heap.insert(task, deadline)
+    }
+
+    /// remove the task behind the handle, returns the task with its deadline
+    pub fn cancel(&mut self, handle: Handle) -> Result<Node<T>, HeapError> {
+        self.heap.remove_by_handle(handle)
+    }
+
+    /// move the task behind the handle to a new deadline
+    pub fn reschedule(&mut self, handle: Handle, deadline: Priority) -> Result<(), HeapError> {
+        self.heap.update_by_handle(handle, deadline)
+    }
+
+    /// deadline of the task behind the handle or None if it left the scheduler
+    pub fn deadline(&self, handle: Handle) -> Option<Priority> {
+        self.heap.get_by_handle(handle).map(|node| node.priority)
+    }
+
+    /// the earliest deadline or None for empty scheduler
+    pub fn next_deadline(&self) -> Option<Priority> {
+        self.heap.data.get(TOP).map(|slot| slot.node.priority)
+    }
+
+    /// tasks with the deadline not after now, earliest first
+    pub fn expired(&mut self, now: Priority) -> Expired<'_, T, D> {
+        Expired {
+            heap: &mut self.heap,
+            now,
+        }
+    }
+
+    /// tasks expired by the current time of the clock
+    pub fn poll(&mut self, clock: &impl Clock) -> Expired<'_, T, D> {
+        self.expired(clock.now())
+    }
+} // Scheduler
+
+impl<T, const D: Index> Default for Scheduler<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const D: Index> Iterator for Expired<'_, T, D> {
+    type Item = Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let top = self.heap.peek_mut()?;
+        if top.priority > self.now {
+            return None;
+        }
+        Some(PeekMut::pop(top))
+    }
+}
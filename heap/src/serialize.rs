@@ -0,0 +1,34 @@
+///
+/// serde support of the heap, enabled by the `serde` feature
+///
+/// the heap is a sequence of its nodes in the heap order, handles are not kept,
+/// deserializing fails when the nodes break the heap order
+///
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Compare, Heap, Index, Node};
+
+impl<T, const D: Index, C, P> Serialize for Heap<T, D, C, P>
+where
+    C: Compare<P>,
+    T: Serialize,
+    P: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T, const D: Index, C, P> Deserialize<'de> for Heap<T, D, C, P>
+where
+    C: Compare<P> + Default,
+    T: Deserialize<'de>,
+    P: Deserialize<'de>,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let data = Vec::<Node<T, P>>::deserialize(deserializer)?;
+        Self::from_heap_order(data, C::default())
+            .ok_or_else(|| De::Error::custom("heap order violated"))
+    }
+}
@@ -0,0 +1,147 @@
+///
+/// heapsort and k-way merge on the D-ary sifts of the heap
+///
+/// expected API and performance:
+///
+///   fn heap_sort(data:&mut [E], cmp)       | O(N log N), in place, not stable   |
+///   fn kmerge(iters, cmp) -> KMerge        | O(log K) per item for K iterators  |
+///
+/// both of them order by `Compare`, the highest element first,
+/// `heap_sort::<_, _, 2>(&mut data, &MinOrder)` sorts ascending
+///
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem;
+
+use crate::{build_heap, push_down, untracked, Compare, Index, TOP};
+
+/// sort the slice in place, the highest element first
+pub fn heap_sort<E, C, const D: Index>(data: &mut [E], cmp: &C)
+where
+    C: Compare<E>,
+{
+    // the lowest element on the top goes to the end first
+    let cmp = |a: &E, b: &E| cmp.higher(b, a);
+    build_heap::<_, _, D>(data, &cmp, &mut untracked);
+
+    for end in (1..data.len()).rev() {
+        data.swap(TOP, end);
+        push_down::<_, _, D>(&mut data[..end], TOP, &cmp, &mut untracked);
+    }
+}
+
+/// next item of one merged iterator with the rest of it
+struct Head<I: Iterator> {
+    item: I::Item,
+    rest: I,
+}
+
+/// order of the heads by their items
+#[derive(Debug)]
+struct ByItem<'a, C>(&'a C);
+
+impl<I, C> Compare<Head<I>> for ByItem<'_, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+    fn higher(&self, a: &Head<I>, b: &Head<I>) -> bool {
+        self.0.higher(&a.item, &b.item)
+    }
+}
+
+/// items of iterators sorted by the comparison merged into one sorted sequence
+pub struct KMerge<I, C, const D: Index = 2>
+where
+    I: Iterator,
+{
+    heads: Vec<Head<I>>,
+    cmp: C,
+}
+
+/// merge iterators each sorted by cmp (the highest item first)
+pub fn kmerge<I, C, const D: Index>(
+    iters: impl IntoIterator<Item = I>,
+    cmp: C,
+) -> KMerge<I::IntoIter, C, D>
+where
+    I: IntoIterator,
+    C: Compare<I::Item>,
+{
+    let mut heads: Vec<Head<I::IntoIter>> = iters
+        .into_iter()
+        .filter_map(|iter| {
+            let mut rest = iter.into_iter();
+            rest.next().map(|item| Head { item, rest })
+        })
+        .collect();
+    build_heap::<_, _, D>(&mut heads, &ByItem(&cmp), &mut untracked);
+
+    KMerge { heads, cmp }
+}
+
+impl<I, C, const D: Index> Iterator for KMerge<I, C, D>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let top = self.heads.first_mut()?;
+        let item = match top.rest.next() {
+            Some(next) => mem::replace(&mut top.item, next),
+            None => self.heads.swap_remove(TOP).item,
+        };
+
+        if !self.heads.is_empty() {
+            push_down::<_, _, D>(&mut self.heads, TOP, &ByItem(&self.cmp), &mut untracked);
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.heads.iter().fold((0, Some(0)), |(low, high), head| {
+            let (rest_low, rest_high) = head.rest.size_hint();
+            (
+                low.saturating_add(rest_low).saturating_add(1),
+                high.zip(rest_high)
+                    .and_then(|(high, rest)| high.checked_add(rest)?.checked_add(1)),
+            )
+        })
+    }
+}
+
+impl<I, C, const D: Index> FusedIterator for KMerge<I, C, D>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+}
+
+impl<I> fmt::Debug for Head<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Head")
+            .field("item", &self.item)
+            .field("rest", &self.rest)
+            .finish()
+    }
+}
+
+impl<I, C, const D: Index> fmt::Debug for KMerge<I, C, D>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KMerge")
+            .field("heads", &self.heads)
+            .field("cmp", &self.cmp)
+            .finish()
+    }
+}
@@ -0,0 +1,145 @@
+///
+/// heap popping nodes of equal priority in insertion order (FIFO)
+///
+/// expected API and performance, same as the wrapped `Heap`:
+///
+///   fn insert(value:T, priority:P) -> Handle    | O(log N) |
+///   fn peek() -> T                              | O(1)     |
+///   fn top() -> T                               | O(log N) |
+///   fn update_by_handle(handle, priority:P)     | O(log N) |
+///   fn remove_by_handle(handle) -> Node         | O(log N) |
+///
+/// every node is stamped by an insertion sequence number,
+/// the lower stamp wins between equal priorities,
+/// an updated node keeps its stamp
+///
+use crate::{Compare, Handle, Heap, HeapError, Index, MinOrder, Node, Priority};
+
+/// priority with the insertion sequence number
+#[derive(Debug, Clone, Copy)]
+struct Stamped<P> {
+    priority: P,
+    seq: u64,
+}
+
+/// order of C, ties broken by the sequence number
+#[derive(Debug, Clone, Copy)]
+struct Fifo<C>(C);
+
+impl<P, C: Compare<P>> Compare<Stamped<P>> for Fifo<C> {
+    fn higher(&self, a: &Stamped<P>, b: &Stamped<P>) -> bool {
+        if self.0.higher(&a.priority, &b.priority) {
+            return true;
+        }
+        !self.0.higher(&b.priority, &a.priority) && a.seq < b.seq
+    }
+}
+
+#[derive(Debug)]
+pub struct StableHeap<T, const D: Index = 2, C = MinOrder, P = Priority>
+where
+    C: Compare<P>,
+{
+    heap: Heap<T, D, Fifo<C>, Stamped<P>>,
+    /// stamp of the next inserted node
+    seq: u64,
+}
+
+impl<T, const D: Index, C, P> StableHeap<T, D, C, P>
+where
+    C: Compare<P>,
+{
+    /// create new heap
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_compare(C::default())
+    }
+
+    /// create new heap ordered by cmp
+    pub fn with_compare(cmp: C) -> Self {
+        Self {
+            heap: Heap::with_compare(Fifo(cmp)),
+            seq: 0,
+        }
+    }
+
+    /// check is heap empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// number of parameters
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// return copy of the top value, panics on empty heap
+    pub fn peek(&self) -> T
+    where
+        T: Clone,
+    {
+        self.heap.peek()
+    }
+
+    /// return reference to the top value or None for empty heap
+    pub fn try_peek(&self) -> Option<&T> {
+        self.heap.try_peek()
+    }
+
+    /// remove and return the top value, panics on empty heap
+    pub fn top(&mut self) -> T {
+        self.heap.top()
+    }
+
+    /// remove and return the top value or None for empty heap
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    /// insert value behind the kept values of the same priority,
+    /// the returned handle refers to the node until it leaves the heap
+    pub fn insert(&mut self, value: T, priority: P) -> Handle {
+        let seq = self.seq;
+        self.seq += 1;
+        self.heap.insert(value, Stamped { priority, seq })
+    }
+
+    /// value and priority behind the handle or None if it left the heap
+    pub fn get_by_handle(&self, handle: Handle) -> Option<(&T, &P)> {
+        self.heap
+            .get_by_handle(handle)
+            .map(|node| (&node.value, &node.priority.priority))
+    }
+
+    /// remove the node behind the handle
+    pub fn remove_by_handle(&mut self, handle: Handle) -> Result<Node<T, P>, HeapError> {
+        let node = self.heap.remove_by_handle(handle)?;
+        Ok(Node {
+            value: node.value,
+            priority: node.priority.priority,
+        })
+    }
+
+    /// change priority of the node behind the handle, the node keeps its insertion order
+    pub fn update_by_handle(&mut self, handle: Handle, priority: P) -> Result<(), HeapError> {
+        let seq = self
+            .heap
+            .get_by_handle(handle)
+            .ok_or(HeapError::InvalidHandle)?
+            .priority
+            .seq;
+        self.heap
+            .update_by_handle(handle, Stamped { priority, seq })
+    }
+} // StableHeap
+
+impl<T, const D: Index, C, P> Default for StableHeap<T, D, C, P>
+where
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
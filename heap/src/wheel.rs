@@ -0,0 +1,183 @@
+///
+/// hierarchical timing wheel, scheduler for very high timer counts
+///
+/// expected API and performance:
+///
+///   fn schedule(deadline, task:T) -> Handle    | O(1)                                  |
+///   fn cancel(handle) -> Node                  | O(1)                                  |
+///   fn reschedule(handle, deadline)            | O(1)                                  |
+///   fn expired(now) -> Vec<Node>               | O(LEVELS * SLOTS + K log K) amortized |
+///
+/// level `l` has SLOTS slots, each of them covering SLOTS^l ticks; a task is
+/// kept on the level of the highest bit group in which its deadline differs
+/// from the current time, as the time goes by the passed slots cascade
+/// into the lower levels, each task moves down at most LEVELS times
+///
+/// K is the number of expired tasks, they are sorted by deadline
+///
+use crate::arena::Arena;
+use crate::scheduler::Clock;
+use crate::{Handle, HeapError, Node, Priority};
+
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = Priority::BITS.div_ceil(SLOT_BITS) as usize;
+/// bucket of the tasks with the deadline not after the current time
+const DUE: usize = LEVELS * SLOTS;
+
+#[derive(Debug)]
+struct Timer<T> {
+    node: Node<T>,
+    bucket: usize,
+    /// position in the bucket
+    slot: usize,
+}
+
+#[derive(Debug)]
+pub struct TimingWheel<T> {
+    timers: Arena<Timer<T>>,
+    /// timer ids, SLOTS buckets per level and the due bucket
+    buckets: Vec<Vec<usize>>,
+    now: Priority,
+}
+
+/// bits of time above the level
+fn above(time: Priority, level: usize) -> Priority {
+    time.checked_shr(SLOT_BITS * (level as u32 + 1))
+        .unwrap_or(0)
+}
+
+/// slot of the time on the level
+fn slot_of(time: Priority, level: usize) -> usize {
+    (time >> (SLOT_BITS * level as u32)) as usize & (SLOTS - 1)
+}
+
+impl<T> TimingWheel<T> {
+    /// create new wheel starting at now
+    pub fn new(now: Priority) -> Self {
+        Self {
+            timers: Arena::new(),
+            buckets: (0..=DUE).map(|_| Vec::new()).collect(),
+            now,
+        }
+    }
+
+    /// check is wheel empty
+    pub fn is_empty(&self) -> bool {
+        self.timers.len() == 0
+    }
+
+    /// number of scheduled tasks
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// the time of the last expiration
+    pub fn now(&self) -> Priority {
+        self.now
+    }
+
+    /// schedule task, the returned handle refers to it until it expires or is cancelled
+    pub fn schedule(&mut self, deadline: Priority, task: T) -> Handle {
+        let id = self.timers.insert(Timer {
+            node: Node {
+                value: task,
+                priority: deadline,
+            },
+            bucket: DUE,
+            slot: 0,
+        });
+        self.put(id);
+        self.timers.handle(id)
+    }
+
+    /// remove the task behind the handle, returns the task with its deadline
+    pub fn cancel(&mut self, handle: Handle) -> Result<Node<T>, HeapError> {
+        let id = self.timers.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.take(id);
+        Ok(self.timers.remove(id).node)
+    }
+
+    /// move the task behind the handle to a new deadline
+    pub fn reschedule(&mut self, handle: Handle, deadline: Priority) -> Result<(), HeapError> {
+        let id = self.timers.id_of(handle).ok_or(HeapError::InvalidHandle)?;
+        self.take(id);
+        self.timers[id].node.priority = deadline;
+        self.put(id);
+        Ok(())
+    }
+
+    /// deadline of the task behind the handle or None if it left the wheel
+    pub fn deadline(&self, handle: Handle) -> Option<Priority> {
+        self.timers
+            .id_of(handle)
+            .map(|id| self.timers[id].node.priority)
+    }
+
+    /// move the time to now and remove the tasks with the deadline not after it,
+    /// earliest first; the time never goes back, an earlier now only drains due tasks
+    pub fn expired(&mut self, now: Priority) -> Vec<Node<T>> {
+        if now > self.now {
+            self.advance(now);
+        }
+
+        let mut expired: Vec<Node<T>> = std::mem::take(&mut self.buckets[DUE])
+            .into_iter()
+            .map(|id| self.timers.remove(id).node)
+            .collect();
+        expired.sort_by_key(|node| node.priority);
+        expired
+    }
+
+    /// tasks expired by the current time of the clock
+    pub fn poll(&mut self, clock: &impl Clock) -> Vec<Node<T>> {
+        self.expired(clock.now())
+    }
+
+    /// cascade the slots passed between the current time and now
+    fn advance(&mut self, now: Priority) {
+        let mut passed = vec![];
+        for level in 0..LEVELS {
+            let slots = if above(now, level) != above(self.now, level) {
+                0..SLOTS
+            } else {
+                // the slots up to the one of the current time are empty
+                slot_of(self.now, level) + 1..slot_of(now, level) + 1
+            };
+
+            for slot in slots {
+                passed.append(&mut self.buckets[level * SLOTS + slot]);
+            }
+        }
+
+        self.now = now;
+        for id in passed {
+            self.put(id);
+        }
+    }
+
+    /// put the timer to the end of the bucket of its deadline
+    fn put(&mut self, id: usize) {
+        let deadline = self.timers[id].node.priority;
+        let bucket = if deadline <= self.now {
+            DUE
+        } else {
+            let level =
+                ((Priority::BITS - 1 - (deadline ^ self.now).leading_zeros()) / SLOT_BITS) as usize;
+            level * SLOTS + slot_of(deadline, level)
+        };
+
+        self.timers[id].bucket = bucket;
+        self.timers[id].slot = self.buckets[bucket].len();
+        self.buckets[bucket].push(id);
+    }
+
+    /// take the timer out of its bucket, the timer itself stays in the arena
+    fn take(&mut self, id: usize) {
+        let (bucket, slot) = (self.timers[id].bucket, self.timers[id].slot);
+        self.buckets[bucket].swap_remove(slot);
+        if let Some(&moved) = self.buckets[bucket].get(slot) {
+            self.timers[moved].slot = slot;
+        }
+    }
+} // TimingWheel
@@ -0,0 +1,89 @@
+use heap::{Codec, Heap, MaxOrder, Node, OrdF64, Priority};
+
+#[cfg(test)]
+mod binary_tests {
+
+    use super::*;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn round_trip() {
+        type Item = String;
+
+        let heap: Heap<Item, 3> = (0..50)
+            .map(|i| (format!("job {i}"), (i * 37 % 50) as Priority))
+            .collect();
+
+        let mut bytes = vec![];
+        heap.write_to(&mut bytes).unwrap();
+
+        let mut read = Heap::<Item, 3>::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read.len(), 50);
+        let original: Vec<Node<Item>> = heap.iter().cloned().collect();
+        let loaded: Vec<Node<Item>> = read.iter().cloned().collect();
+        assert_eq!(loaded, original);
+
+        assert_eq!(read.top(), "job 0");
+        assert_eq!(read.top(), "job 23");
+    }
+
+    #[test]
+    fn layout() {
+        let mut heap = Heap::<u8, 2, MaxOrder, u16>::new();
+        heap.insert(7, 0x0102);
+
+        let mut bytes = vec![];
+        heap.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes, vec![1, 0, 0, 0, 0, 0, 0, 0, 7, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn codecs() {
+        let mut bytes = vec![];
+        (true, 'x').encode(&mut bytes).unwrap();
+        vec![OrdF64(1.5), OrdF64(-2.0)].encode(&mut bytes).unwrap();
+        (-5i64, ()).encode(&mut bytes).unwrap();
+
+        let mut reader = bytes.as_slice();
+        assert_eq!(<(bool, char)>::decode(&mut reader).unwrap(), (true, 'x'));
+        let floats = Vec::<OrdF64>::decode(&mut reader).unwrap();
+        assert_eq!(
+            floats.iter().map(|f| f.0).collect::<Vec<f64>>(),
+            vec![1.5, -2.0]
+        );
+        assert_eq!(<(i64, ())>::decode(&mut reader).unwrap(), (-5, ()));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn invalid() {
+        // root 5 above a child 1 of a min heap
+        let mut bytes = vec![];
+        vec![
+            Node::<u32> {
+                value: 0,
+                priority: 5,
+            },
+            Node {
+                value: 1,
+                priority: 1,
+            },
+        ]
+        .encode(&mut bytes)
+        .unwrap();
+
+        let error = Heap::<u32>::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        // the same nodes are a valid max heap
+        let heap = Heap::<u32, 2, MaxOrder>::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(heap.try_peek(), Some(&0));
+
+        let error = Heap::<u32>::read_from(&mut &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+        // a huge length prefix with no data
+        let error = Heap::<String>::read_from(&mut &u64::MAX.to_le_bytes()[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
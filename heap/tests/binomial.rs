@@ -0,0 +1,45 @@
+use heap::{BinomialHeap, Priority};
+
+#[macro_use]
+mod common;
+
+#[cfg(test)]
+mod binomial_tests {
+
+    use super::*;
+
+    handle_heap_tests!(BinomialHeap);
+
+    #[test]
+    fn update_deep() {
+        type Item = u32;
+
+        // 2^6 nodes make a single tree of degree 6
+        let mut heap = BinomialHeap::<Item, heap::MaxOrder>::new();
+        let handles: Vec<_> = (0..64).map(|i| heap.insert(i, i as Priority)).collect();
+        assert_eq!(heap.peek(), 63);
+
+        // raise a leaf above everything
+        assert_eq!(heap.update_by_handle(handles[0], 100), Ok(()));
+        assert_eq!(heap.peek(), 0);
+
+        // lower the top below everything
+        assert_eq!(heap.update_by_handle(handles[0], 0), Ok(()));
+        assert_eq!(heap.peek(), 63);
+        assert_eq!(
+            heap.remove_by_handle(handles[0]).map(|node| node.value),
+            Ok(0)
+        );
+        assert_eq!(heap.update_by_handle(handles[63], 0), Ok(()));
+        assert_eq!(heap.peek(), 62);
+
+        for (i, &handle) in handles.iter().enumerate().skip(1) {
+            assert_eq!(heap.get_by_handle(handle).unwrap().value, i as Item);
+        }
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        let mut expected: Vec<Item> = (1..63).rev().collect();
+        expected.push(63);
+        assert_eq!(order, expected);
+    }
+}
@@ -0,0 +1,89 @@
+use heap::{BoundedHeap, MaxOrder, Node, Priority};
+
+#[cfg(test)]
+mod bounded_tests {
+
+    use super::*;
+
+    #[test]
+    fn is_empty() {
+        type Item = i32;
+
+        let mut heap = BoundedHeap::<Item>::new(2);
+        assert!(heap.is_empty());
+        assert!(!heap.is_full());
+        assert_eq!(heap.capacity(), 2);
+        assert!(heap.worst().is_none());
+
+        assert_eq!(heap.insert(1, 1), None);
+        assert!(!heap.is_empty());
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut heap = BoundedHeap::<char>::new(0);
+        assert!(heap.is_full());
+        assert_eq!(
+            heap.insert('a', 1),
+            Some(Node {
+                value: 'a',
+                priority: 1
+            })
+        );
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn evict() {
+        type Item = char;
+
+        // the three lowest priorities (nearest neighbours)
+        let mut heap = BoundedHeap::<Item>::new(3);
+        assert_eq!(heap.insert('e', 5), None);
+        assert_eq!(heap.insert('b', 2), None);
+        assert_eq!(heap.insert('d', 4), None);
+        assert!(heap.is_full());
+        assert_eq!(heap.worst().unwrap().value, 'e');
+
+        // a better node evicts the worst
+        let evicted = heap.insert('a', 1).unwrap();
+        assert_eq!((evicted.value, evicted.priority), ('e', 5));
+        // a worse or equal node is rejected
+        let rejected = heap.insert('f', 6).unwrap();
+        assert_eq!(rejected.value, 'f');
+        let rejected = heap.insert('x', 4).unwrap();
+        assert_eq!(rejected.value, 'x');
+        assert_eq!(heap.len(), 3);
+
+        let values: Vec<Item> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|n| n.value)
+            .collect();
+        assert_eq!(values, vec!['a', 'b', 'd']);
+    }
+
+    #[test]
+    fn top_k() {
+        type Item = u64;
+
+        // leaderboard, the highest scores
+        let mut heap = BoundedHeap::<Item, 4, MaxOrder>::new(10);
+        let scores: Vec<Priority> = (0..200).map(|i| (i * 7919) % 1000).collect();
+        for (player, &score) in scores.iter().enumerate() {
+            heap.insert(player as Item, score);
+        }
+
+        let mut expected = scores.clone();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        expected.truncate(10);
+
+        let kept: Vec<Priority> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|n| n.priority)
+            .collect();
+        assert_eq!(kept, expected);
+    }
+}
@@ -0,0 +1,189 @@
+use heap::{BucketQueue, Heap, HeapError, Priority};
+
+#[cfg(test)]
+mod bucket_tests {
+
+    use super::*;
+
+    #[test]
+    fn is_empty() {
+        type Item = i32;
+
+        let mut queue = BucketQueue::<Item>::new(10);
+        assert!(queue.is_empty());
+        assert_eq!(queue.max_priority(), 10);
+        assert_eq!(queue.try_peek(), None);
+        assert_eq!(queue.pop(), None);
+
+        queue.insert(1, 0).unwrap();
+        assert!(!queue.is_empty());
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty heap")]
+    fn top_empty() {
+        let mut queue = BucketQueue::<i32>::new(10);
+        queue.top();
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut queue = BucketQueue::<i32>::new(10);
+        assert_eq!(queue.insert(1, 11), Err(HeapError::OutOfRange));
+        assert!(queue.is_empty());
+
+        let handle = queue.insert(1, 10).unwrap();
+        assert_eq!(
+            queue.update_by_handle(handle, 11),
+            Err(HeapError::OutOfRange)
+        );
+        assert_eq!(queue.update(1, 11), Err(HeapError::OutOfRange));
+        assert_eq!(queue.get_by_handle(handle).unwrap().priority, 10);
+        assert_eq!(queue.top(), 1);
+    }
+
+    #[test]
+    fn top() {
+        type Item = i32;
+
+        let mut queue = BucketQueue::<Item>::new(10);
+        for (value, priority) in [(4, 4), (2, 2), (3, 3), (1, 1), (5, 5)] {
+            queue.insert(value, priority).unwrap();
+        }
+
+        assert_eq!(queue.peek(), 1);
+        assert_eq!(queue.top(), 1);
+        assert_eq!(queue.top(), 2);
+        // lower than the last removed one
+        queue.insert(0, 0).unwrap();
+        assert_eq!(queue.top(), 0);
+        assert_eq!(queue.top(), 3);
+        assert_eq!(queue.top(), 4);
+        assert_eq!(queue.top(), 5);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn peek_after_removal() {
+        type Item = char;
+
+        let mut queue = BucketQueue::<Item>::new(10);
+        let a = queue.insert('a', 2).unwrap();
+        let b = queue.insert('b', 5).unwrap();
+
+        // the lowest bucket empties by remove, update and pop
+        assert_eq!(queue.remove_by_handle(a).map(|node| node.value), Ok('a'));
+        assert_eq!(queue.try_peek(), Some(&'b'));
+        assert_eq!(queue.update_by_handle(b, 9), Ok(()));
+        queue.insert('c', 7).unwrap();
+        assert_eq!(queue.try_peek(), Some(&'c'));
+        assert_eq!(queue.pop(), Some('c'));
+        assert_eq!(queue.try_peek(), Some(&'b'));
+        assert_eq!(queue.pop(), Some('b'));
+        assert_eq!(queue.try_peek(), None);
+
+        queue.insert('d', 10).unwrap();
+        assert_eq!(queue.try_peek(), Some(&'d'));
+        queue.insert('e', 0).unwrap();
+        assert_eq!(queue.try_peek(), Some(&'e'));
+    }
+
+    #[test]
+    fn remove() {
+        type Item = char;
+
+        let mut queue = BucketQueue::<Item>::new(5);
+        assert_eq!(queue.remove('a'), Err(HeapError::Empty));
+
+        for (value, priority) in [('a', 1), ('b', 1), ('c', 2), ('d', 3)] {
+            queue.insert(value, priority).unwrap();
+        }
+
+        let node = queue.remove('a').unwrap();
+        assert_eq!((node.value, node.priority), ('a', 1));
+        assert_eq!(queue.remove('a'), Err(HeapError::NotFound));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.top(), 'b');
+        assert_eq!(queue.top(), 'c');
+    }
+
+    #[test]
+    fn update() {
+        type Item = char;
+
+        let mut queue = BucketQueue::<Item>::new(5);
+        assert_eq!(queue.update('a', 0), Err(HeapError::Empty));
+
+        for (value, priority) in [('a', 1), ('b', 2), ('c', 3)] {
+            queue.insert(value, priority).unwrap();
+        }
+
+        assert_eq!(queue.update('c', 0), Ok(()));
+        assert_eq!(queue.update('a', 5), Ok(()));
+        assert_eq!(queue.update('x', 5), Err(HeapError::NotFound));
+
+        let order: Vec<Item> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(order, vec!['c', 'b', 'a']);
+    }
+
+    #[test]
+    fn handles() {
+        type Item = u32;
+
+        let mut queue = BucketQueue::<Item>::new(100);
+        let handles: Vec<_> = (0..20)
+            .map(|i| queue.insert(i, 10 + i as Priority).unwrap())
+            .collect();
+
+        assert_eq!(queue.update_by_handle(handles[15], 1), Ok(()));
+        assert_eq!(queue.peek(), 15);
+        assert_eq!(queue.get_by_handle(handles[15]).unwrap().priority, 1);
+
+        let node = queue.remove_by_handle(handles[3]).unwrap();
+        assert_eq!((node.value, node.priority), (3, 13));
+        assert_eq!(queue.get_by_handle(handles[3]), None);
+        assert_eq!(
+            queue.remove_by_handle(handles[3]),
+            Err(HeapError::InvalidHandle)
+        );
+        assert_eq!(
+            queue.update_by_handle(handles[3], 0),
+            Err(HeapError::InvalidHandle)
+        );
+
+        // a removed handle does not refer to the node reusing its slot
+        queue.insert(100, 0).unwrap();
+        assert_eq!(queue.get_by_handle(handles[3]), None);
+
+        let order: Vec<Item> = std::iter::from_fn(|| queue.pop()).collect();
+        let mut expected: Vec<Item> = vec![100, 15];
+        expected.extend((0..20).filter(|&i| i != 3 && i != 15));
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn foreign_handle() {
+        let mut queue = BucketQueue::<char>::new(10);
+        let mut other = BucketQueue::<char>::new(10);
+        let mut heap = Heap::<char>::new();
+        queue.insert('q', 5).unwrap();
+        let in_other = other.insert('o', 5).unwrap();
+        let in_heap = heap.insert('h', 5);
+
+        // the same slot in another queue or heap
+        for handle in [in_other, in_heap] {
+            assert_eq!(queue.get_by_handle(handle), None);
+            assert_eq!(
+                queue.update_by_handle(handle, 0),
+                Err(HeapError::InvalidHandle)
+            );
+            assert_eq!(
+                queue.remove_by_handle(handle),
+                Err(HeapError::InvalidHandle)
+            );
+        }
+        assert_eq!(queue.top(), 'q');
+        assert_eq!(other.top(), 'o');
+    }
+}
@@ -0,0 +1,120 @@
+use heap::{channel, channel_with_compare, MaxOrder, Node};
+
+#[cfg(test)]
+mod channel_tests {
+
+    use super::*;
+    use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn priority_order() {
+        type Item = char;
+
+        let (tx, rx) = channel::<Item>();
+        for (value, priority) in [('c', 3), ('a', 1), ('d', 4), ('b', 2)] {
+            tx.send(value, priority).unwrap();
+        }
+
+        assert_eq!(rx.recv(), Ok('a'));
+        assert_eq!(rx.try_recv(), Ok('b'));
+        assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok('c'));
+        assert_eq!(rx.recv(), Ok('d'));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn disconnect_senders() {
+        type Item = i32;
+
+        let (tx, rx) = channel::<Item>();
+        let tx2 = tx.clone();
+        tx.send(1, 1).unwrap();
+        drop(tx);
+        tx2.send(2, 2).unwrap();
+        drop(tx2);
+
+        // pending values are received before the disconnection
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Ok(2));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn disconnect_receiver() {
+        type Item = i32;
+
+        let (tx, rx) = channel::<Item>();
+        tx.send(1, 1).unwrap();
+        drop(rx);
+
+        let error = tx.send(2, 5).unwrap_err();
+        assert_eq!(
+            error.0,
+            Node {
+                value: 2,
+                priority: 5
+            }
+        );
+    }
+
+    #[test]
+    fn threads() {
+        type Item = u64;
+
+        let (tx, rx) = channel_with_compare::<Item, 4, _, u64>(MaxOrder);
+        let producers: Vec<_> = (0..4)
+            .map(|p| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..500 {
+                        tx.send(p * 500 + i, i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+
+        // blocks until the producers send, ends when all of them are done
+        let mut received: Vec<Item> = std::iter::from_fn(|| rx.recv().ok()).collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        received.sort_unstable();
+        assert_eq!(received, (0..2000).collect::<Vec<Item>>());
+    }
+
+    #[test]
+    fn unbounded_timeout() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1, 1).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::MAX), Ok(1));
+
+        drop(tx);
+        assert_eq!(
+            rx.recv_timeout(Duration::MAX),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn wake_up() {
+        let (tx, rx) = channel::<&str>();
+        let receiver = thread::spawn(move || rx.recv_timeout(Duration::from_secs(10)));
+
+        thread::sleep(Duration::from_millis(20));
+        tx.send("late", 0).unwrap();
+        assert_eq!(receiver.join().unwrap(), Ok("late"));
+    }
+}
@@ -0,0 +1,247 @@
+// helpers shared by the integration tests, each test crate uses a part of them
+
+/// linear congruential generator, enough for a shuffle
+pub fn lcg(seed: u64) -> impl FnMut() -> u64 {
+    let mut seed = seed;
+    move || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        seed >> 33
+    }
+}
+
+/// tests of the handle based heaps with `insert`, `meld` and `*_by_handle`,
+/// expanded inside the test module of the heap
+#[allow(unused_macros)]
+macro_rules! handle_heap_tests {
+    ($heap:ident) => {
+        #[test]
+        fn is_empty() {
+            type Item = i32;
+
+            let mut heap = $heap::<Item>::new();
+            assert!(heap.is_empty());
+            assert_eq!(heap.try_peek(), None);
+            assert_eq!(heap.pop(), None);
+
+            heap.insert(1, 0);
+            assert!(!heap.is_empty());
+            assert_eq!(heap.len(), 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "empty heap")]
+        fn top_empty() {
+            let mut heap = $heap::<i32>::new();
+            heap.top();
+        }
+
+        #[test]
+        fn top() {
+            type Item = i32;
+
+            let mut heap = $heap::<Item>::new();
+            for (value, priority) in [(4, 4), (2, 2), (3, 3), (1, 1), (5, 5)] {
+                heap.insert(value, priority);
+            }
+
+            assert_eq!(heap.peek(), 1);
+            assert_eq!(heap.top(), 1);
+            assert_eq!(heap.top(), 2);
+            assert_eq!(heap.top(), 3);
+            assert_eq!(heap.top(), 4);
+            assert_eq!(heap.top(), 5);
+            assert!(heap.is_empty());
+        }
+
+        #[test]
+        fn update() {
+            type Item = u32;
+
+            let mut heap = $heap::<Item>::new();
+            let handles: Vec<_> = (0..20)
+                .map(|i| heap.insert(i, 10 + i as heap::Priority))
+                .collect();
+            heap.top();
+
+            // decrease-key
+            assert_eq!(heap.update_by_handle(handles[15], 1), Ok(()));
+            assert_eq!(heap.peek(), 15);
+            // increase-key of the top
+            assert_eq!(heap.update_by_handle(handles[15], 100), Ok(()));
+            assert_eq!(heap.peek(), 1);
+            // increase-key inside
+            assert_eq!(heap.update_by_handle(handles[5], 50), Ok(()));
+
+            assert_eq!(
+                heap.update_by_handle(handles[0], 0),
+                Err(heap::HeapError::InvalidHandle)
+            );
+            assert_eq!(heap.get_by_handle(handles[5]).unwrap().priority, 50);
+
+            let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+            let mut expected: Vec<Item> = (1..20).filter(|&i| i != 5 && i != 15).collect();
+            expected.push(5);
+            expected.push(15);
+            assert_eq!(order, expected);
+        }
+
+        #[test]
+        fn remove() {
+            type Item = char;
+
+            let mut heap = $heap::<Item, heap::MaxOrder>::new();
+            let a = heap.insert('a', 1);
+            let b = heap.insert('b', 2);
+            let c = heap.insert('c', 3);
+            let d = heap.insert('d', 4);
+            heap.top();
+
+            let node = heap.remove_by_handle(b).unwrap();
+            assert_eq!(node.value, 'b');
+            assert_eq!(node.priority, 2);
+            assert_eq!(
+                heap.remove_by_handle(b),
+                Err(heap::HeapError::InvalidHandle)
+            );
+            assert_eq!(
+                heap.remove_by_handle(d),
+                Err(heap::HeapError::InvalidHandle)
+            );
+            assert!(heap.get_by_handle(b).is_none());
+
+            // a reused slot does not revive the stale handle
+            let e = heap.insert('e', 0);
+            assert!(heap.get_by_handle(b).is_none() && heap.get_by_handle(d).is_none());
+            assert_eq!(heap.get_by_handle(e).unwrap().value, 'e');
+
+            assert_eq!(heap.remove_by_handle(c).map(|node| node.value), Ok('c'));
+            assert_eq!(heap.top(), 'a');
+            assert!(heap.get_by_handle(a).is_none());
+            assert_eq!(heap.top(), 'e');
+        }
+
+        #[test]
+        fn foreign_handle() {
+            type Item = char;
+
+            let mut a = $heap::<Item>::new();
+            let mut b = $heap::<Item>::new();
+            let in_a = a.insert('a', 1);
+            b.insert('b', 1);
+
+            // the same slot in another heap
+            assert!(b.get_by_handle(in_a).is_none());
+            assert_eq!(
+                b.update_by_handle(in_a, 0),
+                Err(heap::HeapError::InvalidHandle)
+            );
+            assert_eq!(
+                b.remove_by_handle(in_a).map(|node| node.value),
+                Err(heap::HeapError::InvalidHandle)
+            );
+            assert_eq!(b.len(), 1);
+
+            // a meld hands the node over with its handle
+            b.meld(a);
+            assert_eq!(b.remove_by_handle(in_a).map(|node| node.value), Ok('a'));
+        }
+
+        #[test]
+        fn meld() {
+            type Item = u32;
+
+            let mut heap = $heap::<Item>::new();
+            let kept = heap.insert(0, 50);
+            let mut other = $heap::<Item>::new();
+            for i in 1..10 {
+                other.insert(i, i as heap::Priority);
+            }
+            let moved = other.insert(11, 60);
+            let removed = other.insert(100, 0);
+            other.remove_by_handle(removed).unwrap();
+
+            heap.meld(other);
+            assert_eq!(heap.len(), 11);
+            assert_eq!(heap.get_by_handle(kept).unwrap().value, 0);
+            assert_eq!(heap.remove_by_handle(moved).unwrap().value, 11);
+            assert!(heap.get_by_handle(removed).is_none());
+
+            heap.insert(10, 0);
+            let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+            assert_eq!(order, vec![10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
+        }
+
+        #[test]
+        fn meld_nested() {
+            type Item = usize;
+
+            let mut heaps = std::collections::VecDeque::new();
+            let mut handles = Vec::new();
+            for i in 0..15 {
+                let mut heap = $heap::<Item>::new();
+                handles.push(heap.insert(i, i as heap::Priority));
+                heaps.push_back(heap);
+            }
+
+            // meld pairs until one heap is left, both sides bring earlier melds
+            while heaps.len() > 1 {
+                let mut heap = heaps.pop_front().unwrap();
+                heap.meld(heaps.pop_front().unwrap());
+                heaps.push_back(heap);
+            }
+
+            let mut heap = heaps.pop_front().unwrap();
+            for (i, &handle) in handles.iter().enumerate() {
+                assert_eq!(heap.get_by_handle(handle).unwrap().value, i);
+            }
+
+            heap.remove_by_handle(handles[0]).unwrap();
+            heap.update_by_handle(handles[9], 0).unwrap();
+            assert_eq!(heap.len(), 14);
+            assert_eq!(heap.top(), 9);
+            assert_eq!(heap.top(), 1);
+        }
+
+        #[test]
+        fn random() {
+            type Item = u64;
+
+            let mut next = crate::common::lcg(42);
+
+            let mut heap = $heap::<Item>::new();
+            // (handle, value, priority)
+            let mut kept: Vec<(heap::Handle, Item, heap::Priority)> = vec![];
+
+            for round in 0..2000 {
+                match next() % 5 {
+                    0 | 1 => {
+                        let priority = next() % 1000;
+                        kept.push((heap.insert(round, priority), round, priority));
+                    }
+                    2 if !kept.is_empty() => {
+                        let i = (next() as usize) % kept.len();
+                        let priority = next() % 1000;
+                        assert_eq!(heap.update_by_handle(kept[i].0, priority), Ok(()));
+                        kept[i].2 = priority;
+                    }
+                    3 if !kept.is_empty() => {
+                        let i = (next() as usize) % kept.len();
+                        let (handle, value, priority) = kept.swap_remove(i);
+                        let node = heap.remove_by_handle(handle).unwrap();
+                        assert_eq!((node.value, node.priority), (value, priority));
+                    }
+                    _ => {
+                        if let Some(value) = heap.pop() {
+                            let i = kept.iter().position(|k| k.1 == value).unwrap();
+                            let (_, _, priority) = kept.swap_remove(i);
+                            assert!(kept.iter().all(|k| k.2 >= priority));
+                        }
+                    }
+                }
+                assert_eq!(heap.len(), kept.len());
+            }
+        }
+    };
+}
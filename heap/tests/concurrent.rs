@@ -0,0 +1,111 @@
+use heap::{ConcurrentHeap, MaxOrder, Priority};
+
+#[cfg(test)]
+mod concurrent_tests {
+
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn is_empty() {
+        type Item = i32;
+
+        let heap = ConcurrentHeap::<Item>::new(4);
+        assert!(heap.is_empty());
+        assert_eq!(heap.shards(), 4);
+        assert_eq!(heap.try_pop(), None);
+
+        heap.push(1, 0);
+        assert!(!heap.is_empty());
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.try_pop(), Some(1));
+        assert_eq!(heap.try_pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one shard")]
+    fn no_shards() {
+        ConcurrentHeap::<i32>::new(0);
+    }
+
+    #[test]
+    fn single_shard() {
+        type Item = char;
+
+        // one shard keeps the exact order
+        let heap = ConcurrentHeap::<Item, 4, MaxOrder>::new(1);
+        for (value, priority) in [('b', 2), ('d', 4), ('a', 1), ('c', 3)] {
+            heap.push(value, priority);
+        }
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.try_pop()).collect();
+        assert_eq!(order, vec!['d', 'c', 'b', 'a']);
+    }
+
+    #[test]
+    fn relaxed() {
+        type Item = u64;
+
+        let heap = ConcurrentHeap::<Item>::new(4);
+        for i in 0..1000 {
+            heap.push(i, i as Priority);
+        }
+
+        let mut order: Vec<Item> = std::iter::from_fn(|| heap.try_pop()).collect();
+        assert_eq!(order.len(), 1000);
+        assert!(heap.is_empty());
+
+        // the first values come from the low end
+        assert!(order[..10].iter().all(|&i| i < 100));
+        order.sort_unstable();
+        assert_eq!(order, (0..1000).collect::<Vec<Item>>());
+    }
+
+    #[test]
+    fn threads() {
+        type Item = u64;
+        const PRODUCERS: u64 = 4;
+        const PER_PRODUCER: u64 = 2500;
+
+        let heap = Arc::new(ConcurrentHeap::<Item, 4>::with_compare(8, heap::MinOrder));
+
+        // consumers block until values arrive
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || {
+                    (0..PRODUCERS * PER_PRODUCER / 4)
+                        .map(|_| heap.pop_wait())
+                        .collect::<Vec<Item>>()
+                })
+            })
+            .collect();
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let value = p * PER_PRODUCER + i;
+                        heap.push(value, value % 97);
+                    }
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let mut popped: Vec<Item> = consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect();
+        popped.sort_unstable();
+
+        assert_eq!(popped, (0..PRODUCERS * PER_PRODUCER).collect::<Vec<Item>>());
+        assert!(heap.is_empty());
+        assert_eq!(heap.try_pop(), None);
+    }
+}
@@ -0,0 +1,34 @@
+use heap::{FibonacciHeap, Priority};
+
+#[macro_use]
+mod common;
+
+#[cfg(test)]
+mod fibonacci_tests {
+
+    use super::*;
+
+    handle_heap_tests!(FibonacciHeap);
+
+    #[test]
+    fn cascading_cut() {
+        type Item = u32;
+
+        let mut heap = FibonacciHeap::<Item>::new();
+        let handles: Vec<_> = (0..64)
+            .map(|i| heap.insert(i, 100 + i as Priority))
+            .collect();
+
+        // consolidate into deep trees
+        assert_eq!(heap.top(), 0);
+
+        // many decrease-keys cut and mark the trees
+        for (i, &handle) in handles.iter().enumerate().skip(1) {
+            assert_eq!(heap.update_by_handle(handle, 100 - i as Priority), Ok(()));
+            assert_eq!(heap.peek(), i as Item);
+        }
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, (1..64).rev().collect::<Vec<Item>>());
+    }
+}
@@ -0,0 +1,169 @@
+use heap::graph::{astar, dijkstra, prim, Adjacency, Edge};
+use heap::Priority;
+
+mod common;
+
+#[cfg(test)]
+mod graph_tests {
+
+    use super::*;
+
+    type Graph = Vec<Vec<(usize, Priority)>>;
+
+    fn undirected(vertices: usize, edges: &[(usize, usize, Priority)]) -> Graph {
+        let mut graph: Graph = vec![vec![]; vertices];
+        for &(a, b, weight) in edges {
+            graph[a].push((b, weight));
+            graph[b].push((a, weight));
+        }
+        graph
+    }
+
+    /// width x height grid, vertex y * width + x, unit weights
+    fn grid(width: usize, height: usize, walls: &[usize]) -> Graph {
+        let mut graph: Graph = vec![vec![]; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let vertex = y * width + x;
+                if walls.contains(&vertex) {
+                    continue;
+                }
+                let mut link = |next: usize| {
+                    if !walls.contains(&next) {
+                        graph[vertex].push((next, 1));
+                    }
+                };
+                if x > 0 {
+                    link(vertex - 1);
+                }
+                if x + 1 < width {
+                    link(vertex + 1);
+                }
+                if y > 0 {
+                    link(vertex - width);
+                }
+                if y + 1 < height {
+                    link(vertex + width);
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn dijkstra_paths() {
+        //   0 --1-- 1 --1-- 2
+        //   |               |
+        //   5               1
+        //   |               |
+        //   3 ------1------ 4     5 (unreachable)
+        let graph = undirected(6, &[(0, 1, 1), (1, 2, 1), (2, 4, 1), (0, 3, 5), (3, 4, 1)]);
+
+        let binary = dijkstra::<_, 2>(&graph, 0);
+        let quaternary = dijkstra::<_, 4>(graph.as_slice(), 0);
+        for paths in [binary, quaternary] {
+            assert_eq!(paths.distance(0), Some(0));
+            assert_eq!(paths.distance(3), Some(4));
+            assert_eq!(paths.path_to(3), Some(vec![0, 1, 2, 4, 3]));
+            assert_eq!(paths.distance(5), None);
+            assert_eq!(paths.path_to(5), None);
+        }
+    }
+
+    #[test]
+    fn astar_grid() {
+        let (width, height) = (8, 6);
+        let walls: Vec<usize> = (0..5).map(|y| y * width + 4).collect();
+        let graph = grid(width, height, &walls);
+        assert_eq!(graph.vertices(), 48);
+
+        let target = 7;
+        let manhattan = |vertex: usize| {
+            let (x, y) = (vertex % width, vertex / width);
+            ((7 - x) + y) as Priority
+        };
+
+        let (length, path) = astar::<_, 2>(&graph, 0, target, manhattan).unwrap();
+        assert_eq!(Some(length), dijkstra::<_, 2>(&graph, 0).distance(target));
+        assert_eq!(length, 17);
+        assert_eq!(path.len(), 18);
+        assert_eq!((path[0], path[17]), (0, target));
+
+        // zero heuristic is plain Dijkstra
+        let (length, _) = astar::<_, 4>(&graph, 0, target, |_| 0).unwrap();
+        assert_eq!(length, 17);
+
+        // walled off
+        let walls: Vec<usize> = (0..height).map(|y| y * width + 4).collect();
+        let graph = grid(width, height, &walls);
+        assert_eq!(astar::<_, 2>(&graph, 0, target, manhattan), None);
+    }
+
+    #[test]
+    fn prim_tree() {
+        let graph = undirected(
+            5,
+            &[
+                (0, 1, 4),
+                (0, 2, 1),
+                (1, 2, 2),
+                (1, 3, 5),
+                (2, 3, 8),
+                (3, 4, 3),
+            ],
+        );
+
+        let tree = prim::<_, 2>(&graph);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.iter().map(|edge| edge.weight).sum::<Priority>(), 11);
+        assert!(tree.contains(&Edge {
+            from: 0,
+            to: 2,
+            weight: 1
+        }));
+    }
+
+    #[test]
+    fn prim_random() {
+        let mut next = common::lcg(42);
+
+        let vertices = 60;
+        let edges: Vec<(usize, usize, Priority)> = (0..300)
+            .map(|_| {
+                let a = next() as usize % vertices;
+                let b = next() as usize % vertices;
+                (a, b, next() % 100)
+            })
+            .filter(|(a, b, _)| a != b)
+            .collect();
+        let graph = undirected(vertices, &edges);
+
+        // Kruskal with union find as the reference
+        let mut sorted = edges.clone();
+        sorted.sort_by_key(|&(_, _, weight)| weight);
+        let mut roots: Vec<usize> = (0..vertices).collect();
+        fn find(roots: &mut [usize], v: usize) -> usize {
+            if roots[v] != v {
+                roots[v] = find(roots, roots[v]);
+            }
+            roots[v]
+        }
+        let (mut weight, mut count) = (0, 0);
+        for (a, b, w) in sorted {
+            let (ra, rb) = (find(&mut roots, a), find(&mut roots, b));
+            if ra != rb {
+                roots[ra] = rb;
+                weight += w;
+                count += 1;
+            }
+        }
+
+        for tree in [prim::<_, 2>(&graph), prim::<_, 4>(&graph)] {
+            assert_eq!(tree.len(), count);
+            assert_eq!(
+                tree.iter().map(|edge| edge.weight).sum::<Priority>(),
+                weight
+            );
+        }
+    }
+}
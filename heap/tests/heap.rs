@@ -1,4 +1,4 @@
-use heap::{Data, Heap, Node, Priority};
+use heap::{Data, Heap, HeapError, MaxOrder, MinOrder, Node, OrdF64, Priority, StableHeap};
 
 #[cfg(test)]
 mod heap_tests {
@@ -82,14 +82,121 @@ mod heap_tests {
         assert_eq!(v, VALUE_4);
     }
 
+    #[test]
+    fn try_peek() {
+        type Item = i32;
+
+        let mut heap = Heap::<Item>::new();
+        assert_eq!(heap.try_peek(), None);
+
+        const VALUE_4: Item = 4;
+        const PRIORITY_4: Priority = 4;
+        heap.insert(VALUE_4, PRIORITY_4);
+        assert_eq!(heap.try_peek(), Some(&VALUE_4));
+
+        const VALUE_2: Item = 2;
+        const PRIORITY_2: Priority = 2;
+        heap.insert(VALUE_2, PRIORITY_2);
+        assert_eq!(heap.try_peek(), Some(&VALUE_2));
+        assert_eq!(heap.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty heap")]
+    fn peek_empty() {
+        let heap = Heap::<i32>::new();
+        heap.peek();
+    }
+
+    #[test]
+    #[should_panic(expected = "empty heap")]
+    fn top_empty() {
+        let mut heap = Heap::<i32>::new();
+        heap.top();
+    }
+
+    #[test]
+    fn pop() {
+        type Item = i32;
+
+        let mut heap = Heap::<Item>::new();
+        assert_eq!(heap.pop(), None);
+
+        const VALUE_4: Item = 4;
+        const PRIORITY_4: Priority = 4;
+        heap.insert(VALUE_4, PRIORITY_4);
+
+        const VALUE_2: Item = 2;
+        const PRIORITY_2: Priority = 2;
+        heap.insert(VALUE_2, PRIORITY_2);
+
+        const VALUE_3: Item = 3;
+        const PRIORITY_3: Priority = 3;
+        heap.insert(VALUE_3, PRIORITY_3);
+
+        assert_eq!(heap.pop(), Some(VALUE_2));
+        assert_eq!(heap.pop(), Some(VALUE_3));
+        assert_eq!(heap.pop(), Some(VALUE_4));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
     #[test]
     fn remove() {
-        // todo!();
+        type Item = i32;
+
+        let mut heap = Heap::<Item>::new();
+        assert_eq!(heap.remove(1), Err(HeapError::Empty));
+
+        for (value, priority) in [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (7, 7)] {
+            heap.insert(value, priority);
+        }
+
+        assert_eq!(heap.remove(8), Err(HeapError::NotFound));
+        assert_eq!(heap.len(), 7);
+
+        let node = heap.remove(4).unwrap();
+        assert_eq!(node.value, 4);
+        assert_eq!(node.priority, 4);
+        assert_eq!(heap.remove(4), Err(HeapError::NotFound));
+
+        // removing the last slot must not touch the rest
+        assert!(heap.remove(7).is_ok());
+        assert!(heap.remove(1).is_ok());
+
+        assert_eq!(heap.top(), 2);
+        assert_eq!(heap.top(), 3);
+        assert_eq!(heap.top(), 5);
+        assert_eq!(heap.top(), 6);
+        assert!(heap.is_empty());
     }
 
     #[test]
     fn update() {
-        // todo!();
+        type Item = i32;
+
+        let mut heap = Heap::<Item>::new();
+        assert_eq!(heap.update(1, 1), Err(HeapError::Empty));
+
+        for (value, priority) in [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)] {
+            heap.insert(value, priority);
+        }
+
+        assert_eq!(heap.update(6, 0), Err(HeapError::NotFound));
+        assert_eq!(heap.peek(), 1);
+
+        assert_eq!(heap.update(5, 0), Ok(()));
+        assert_eq!(heap.peek(), 5);
+
+        assert_eq!(heap.update(5, 10), Ok(()));
+        assert_eq!(heap.update(1, 6), Ok(()));
+
+        assert_eq!(heap.top(), 2);
+        assert_eq!(heap.top(), 3);
+        assert_eq!(heap.top(), 4);
+        assert_eq!(heap.top(), 1);
+        assert_eq!(heap.top(), 5);
+        assert_eq!(heap.pop(), None);
     }
 
     #[test]
@@ -328,4 +435,379 @@ mod heap_tests {
             }
         }
     }
+
+    #[test]
+    fn max_order() {
+        type Item = i32;
+
+        {
+            const D: heap::Index = 2;
+
+            let mut heap = Heap::<Item, D, MaxOrder>::new();
+            for (value, priority) in [(1, 1), (4, 4), (2, 2), (5, 5), (3, 3)] {
+                heap.insert(value, priority);
+            }
+
+            assert_eq!(heap.update(1, 6), Ok(()));
+            assert_eq!(heap.update(5, 0), Ok(()));
+
+            assert_eq!(heap.top(), 1);
+            assert_eq!(heap.top(), 4);
+            assert_eq!(heap.top(), 3);
+            assert_eq!(heap.top(), 2);
+            assert_eq!(heap.top(), 5);
+            assert_eq!(heap.pop(), None);
+        }
+        {
+            const D: heap::Index = 3;
+
+            let data: Data<Item> = (0..10)
+                .map(|i| Node {
+                    value: i,
+                    priority: i as Priority,
+                })
+                .collect();
+
+            let mut heap = Heap::<Item, D, MaxOrder>::new_from(data);
+            for i in (0..10).rev() {
+                assert_eq!(heap.top(), i);
+            }
+        }
+    }
+
+    #[test]
+    fn closure_order() {
+        type Item = i32;
+        const D: heap::Index = 4;
+
+        // even priorities first, then the lowest one
+        let cmp = |a: &Priority, b: &Priority| (a % 2, a) < (b % 2, b);
+        let mut heap = Heap::<Item, D, _>::with_compare(cmp);
+
+        for i in 0..8 {
+            heap.insert(i, i as Priority);
+        }
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, vec![0, 2, 4, 6, 1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn heapify_by() {
+        type Item = i32;
+        const D: heap::Index = 2;
+
+        let mut data: Data<Item> = (0..7)
+            .map(|i| Node {
+                value: i,
+                priority: i as Priority,
+            })
+            .collect();
+
+        heap::heapify_by::<Item, Priority, MaxOrder, D>(&mut data, &MaxOrder);
+        assert_eq!(data[0].value, 6);
+
+        heap::heapify_by::<Item, Priority, MinOrder, D>(&mut data, &MinOrder);
+        assert_eq!(data[0].value, 0);
+
+        let mut empty: Data<Item> = vec![];
+        heap::heapify::<Item, D>(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn generic_priority() {
+        type Item = &'static str;
+        const D: heap::Index = 2;
+
+        {
+            let mut heap = Heap::<Item, D, MinOrder, OrdF64>::new();
+            heap.insert("b", OrdF64(0.5));
+            heap.insert("c", OrdF64(f64::INFINITY));
+            heap.insert("a", OrdF64(-1.25));
+            heap.insert("d", OrdF64(f64::NAN));
+
+            assert_eq!(heap.update("b", OrdF64(-2.0)), Ok(()));
+
+            assert_eq!(heap.top(), "b");
+            assert_eq!(heap.top(), "a");
+            assert_eq!(heap.top(), "c");
+            assert_eq!(heap.top(), "d");
+        }
+        {
+            // (deadline, rank)
+            let mut heap = Heap::<Item, D, MaxOrder, (u32, i8)>::new();
+            heap.insert("a", (1, 0));
+            heap.insert("b", (2, -1));
+            heap.insert("c", (2, 3));
+
+            assert_eq!(heap.top(), "c");
+            assert_eq!(heap.top(), "b");
+            assert_eq!(heap.top(), "a");
+        }
+    }
+
+    #[test]
+    fn handles() {
+        type Item = &'static str;
+
+        let mut heap = Heap::<Item, 3>::new();
+
+        // equal values are told apart by their handles
+        let job_a = heap.insert("job", 5);
+        let job_b = heap.insert("job", 7);
+        let other = heap.insert("other", 6);
+        for i in 10..20 {
+            heap.insert("filler", i);
+        }
+
+        assert_eq!(heap.get_by_handle(job_b).unwrap().priority, 7);
+        assert_eq!(heap.update_by_handle(job_b, 1), Ok(()));
+        assert_eq!(heap.get_by_handle(job_b).unwrap().priority, 1);
+        assert_eq!(heap.get_by_handle(job_a).unwrap().priority, 5);
+
+        let node = heap.remove_by_handle(other).unwrap();
+        assert_eq!(node.value, "other");
+        assert_eq!(node.priority, 6);
+        assert!(heap.get_by_handle(other).is_none());
+        assert_eq!(heap.remove_by_handle(other), Err(HeapError::InvalidHandle));
+        assert_eq!(heap.update_by_handle(other, 0), Err(HeapError::InvalidHandle));
+
+        assert_eq!(heap.top(), "job");
+        assert!(heap.get_by_handle(job_b).is_none());
+        assert_eq!(heap.get_by_handle(job_a).unwrap().priority, 5);
+
+        // a reused slot does not revive the stale handle
+        let again = heap.insert("again", 0);
+        assert!(heap.get_by_handle(job_b).is_none());
+        assert_eq!(heap.get_by_handle(again).unwrap().value, "again");
+
+        assert_eq!(heap.top(), "again");
+        assert_eq!(heap.top(), "job");
+        assert_eq!(heap.len(), 10);
+    }
+
+    #[test]
+    fn foreign_handle() {
+        type Item = &'static str;
+
+        let mut a = Heap::<Item>::new();
+        let mut b = Heap::<Item>::new();
+        let in_a = a.insert("in a", 5);
+        b.insert("in b", 5);
+
+        // the same id and generation in another heap
+        assert!(b.get_by_handle(in_a).is_none());
+        assert_eq!(b.update_by_handle(in_a, 0), Err(HeapError::InvalidHandle));
+        assert_eq!(b.remove_by_handle(in_a), Err(HeapError::InvalidHandle));
+        assert_eq!(b.len(), 1);
+        assert_eq!(a.remove_by_handle(in_a).map(|node| node.value), Ok("in a"));
+    }
+
+    #[test]
+    fn handles_follow_moves() {
+        type Item = u32;
+        const D: heap::Index = 2;
+
+        let mut heap = Heap::<Item, D>::new();
+        let handles: Vec<_> = (0..64).map(|i| heap.insert(i, 64 - i as Priority)).collect();
+
+        for (i, &handle) in handles.iter().enumerate() {
+            let node = heap.get_by_handle(handle).unwrap();
+            assert_eq!(node.value, i as Item);
+            assert_eq!(node.priority, 64 - i as Priority);
+        }
+
+        for &handle in handles.iter().step_by(2) {
+            assert!(heap.remove_by_handle(handle).is_ok());
+        }
+
+        for (i, &handle) in handles.iter().enumerate().skip(1).step_by(2) {
+            assert_eq!(heap.get_by_handle(handle).unwrap().value, i as Item);
+        }
+
+        // rebuilding the heap drops every handle
+        heap.new_heap(vec![Node {
+            value: 100,
+            priority: 0,
+        }]);
+        assert!(handles.iter().all(|&handle| heap.get_by_handle(handle).is_none()));
+        assert_eq!(heap.top(), 100);
+    }
+
+    #[test]
+    fn not_clone() {
+        // neither Clone nor Debug nor PartialEq
+        struct Payload {
+            id: u32,
+            _buffer: Vec<u8>,
+        }
+
+        let mut heap = Heap::<Payload, 3>::new();
+        let mut handles = vec![];
+        for id in 0..32 {
+            let payload = Payload {
+                id,
+                _buffer: vec![0; 16],
+            };
+            handles.push(heap.insert(payload, (id * 7 % 32) as Priority));
+        }
+
+        assert_eq!(heap.try_peek().map(|payload| payload.id), Some(0));
+        assert_eq!(heap.update_by_handle(handles[5], 0), Ok(()));
+        assert_eq!(heap.remove_by_handle(handles[0]).map(|node| node.value.id).ok(), Some(0));
+
+        assert_eq!(heap.pop().map(|payload| payload.id), Some(5));
+        let mut last = 0;
+        while let Some(payload) = heap.pop() {
+            let priority = (payload.id * 7 % 32) as Priority;
+            assert!(priority >= last);
+            last = priority;
+        }
+    }
+
+    #[test]
+    fn append() {
+        type Item = u32;
+        const D: heap::Index = 2;
+
+        let mut heap = Heap::<Item, D>::new();
+        let kept = heap.insert(100, 50);
+        for i in 0..4 {
+            heap.insert(i, (i * 2) as Priority);
+        }
+
+        // small other, sifted one by one
+        let mut other = Heap::<Item, D>::new();
+        let lost = other.insert(7, 1);
+        heap.append(&mut other);
+        assert!(other.is_empty());
+        assert!(other.get_by_handle(lost).is_none());
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.get_by_handle(kept).unwrap().value, 100);
+
+        // big other, rebuilt at once
+        let mut other: Heap<Item, D> = (200..400).map(|i| (i, i as Priority)).collect();
+        heap.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(heap.len(), 206);
+        assert_eq!(heap.get_by_handle(kept).unwrap().priority, 50);
+
+        assert_eq!(heap.top(), 0);
+        assert_eq!(heap.top(), 7);
+        let priorities: Vec<Priority> = heap.drain_sorted().map(|node| node.priority).collect();
+        assert!(priorities.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn append_to_small() {
+        type Item = u32;
+
+        // many nodes after a few, rebuilt at once
+        for size in 1..8 {
+            let mut heap = Heap::<Item>::new();
+            let handles: Vec<_> = (0..size)
+                .map(|i| heap.insert(i, 500 - i as Priority))
+                .collect();
+
+            let mut other: Heap<Item> = (100..200)
+                .map(|i| (i, (i * 37 % 100 + 1) as Priority))
+                .collect();
+            heap.append(&mut other);
+            assert_eq!(heap.len(), size as usize + 100);
+            for (i, &handle) in handles.iter().enumerate() {
+                assert_eq!(heap.get_by_handle(handle).unwrap().value, i as Item);
+            }
+
+            assert_eq!(heap.update_by_handle(handles[0], 0), Ok(()));
+            assert_eq!(heap.top(), 0);
+            let priorities: Vec<Priority> =
+                heap.drain_sorted().map(|node| node.priority).collect();
+            assert_eq!(priorities.len(), size as usize + 99);
+            assert!(priorities.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn merge() {
+        type Item = char;
+
+        let a: Heap<Item, 3, MaxOrder> = [('a', 1), ('c', 3)].into_iter().collect();
+        let b: Heap<Item, 3, MaxOrder> = [('b', 2), ('d', 4)].into_iter().collect();
+
+        let mut heap = a.merge(b);
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.top(), 'd');
+        assert_eq!(heap.top(), 'c');
+        assert_eq!(heap.top(), 'b');
+        assert_eq!(heap.top(), 'a');
+    }
+
+    #[test]
+    fn merge_many() {
+        type Item = usize;
+        const D: heap::Index = 4;
+
+        // one heap per worker
+        let workers: Vec<Heap<Item, D>> = (0..8)
+            .map(|w| (0..10).map(|i| (w * 10 + i, ((i * 8 + w) % 80) as Priority)).collect())
+            .collect();
+
+        let mut heap = Heap::<Item, D>::new();
+        let handle = heap.insert(1000, 1000);
+        let mut heap = heap.merge_many(workers);
+
+        assert_eq!(heap.len(), 81);
+        assert_eq!(heap.get_by_handle(handle).unwrap().value, 1000);
+
+        let priorities: Vec<Priority> = heap.drain_sorted().map(|node| node.priority).collect();
+        let mut expected: Vec<Priority> = (0..80).collect();
+        expected.push(1000);
+        assert_eq!(priorities, expected);
+    }
+
+    #[test]
+    fn stable() {
+        type Item = u32;
+
+        // jobs of three priorities, inserted interleaved
+        let mut heap = StableHeap::<Item>::new();
+        for job in 0..30 {
+            heap.insert(job, (job % 3) as Priority);
+        }
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        let mut expected: Vec<Item> = vec![];
+        for priority in 0..3 {
+            expected.extend((0..30).filter(|job| job % 3 == priority));
+        }
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn stable_handles() {
+        type Item = char;
+
+        let mut heap = StableHeap::<Item, 4, MaxOrder>::new();
+        let a = heap.insert('a', 1);
+        let b = heap.insert('b', 2);
+        heap.insert('c', 2);
+        heap.insert('d', 1);
+
+        // updated node keeps its insertion order
+        assert_eq!(heap.update_by_handle(a, 2), Ok(()));
+        assert_eq!(heap.get_by_handle(a), Some((&'a', &2)));
+
+        let node = heap.remove_by_handle(b).unwrap();
+        assert_eq!((node.value, node.priority), ('b', 2));
+        assert_eq!(heap.get_by_handle(b), None);
+        assert_eq!(heap.update_by_handle(b, 0), Err(HeapError::InvalidHandle));
+
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.peek(), 'a');
+        assert_eq!(heap.top(), 'a');
+        assert_eq!(heap.top(), 'c');
+        assert_eq!(heap.top(), 'd');
+        assert!(heap.is_empty());
+    }
 }
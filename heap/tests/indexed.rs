@@ -0,0 +1,146 @@
+use heap::{HeapError, IndexedHeap, MaxOrder, Priority};
+
+#[cfg(test)]
+mod indexed_tests {
+
+    use super::*;
+
+    #[test]
+    fn insert() {
+        type Item = char;
+
+        let mut heap = IndexedHeap::<Item>::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.try_peek(), None);
+
+        assert_eq!(heap.insert('c', 3), None);
+        assert_eq!(heap.insert('a', 1), None);
+        assert_eq!(heap.insert('b', 2), None);
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.peek(), 'a');
+
+        // the same value again changes its priority
+        assert_eq!(heap.insert('c', 0), Some(3));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.peek(), 'c');
+
+        assert_eq!(heap.top(), 'c');
+        assert_eq!(heap.top(), 'a');
+        assert_eq!(heap.top(), 'b');
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn contains() {
+        type Item = String;
+
+        let mut heap = IndexedHeap::<Item, 3>::new();
+        for (i, name) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+            heap.insert(name.to_string(), i as Priority);
+        }
+
+        assert!(heap.contains(&"c".to_string()));
+        assert!(!heap.contains(&"f".to_string()));
+        assert_eq!(heap.priority(&"d".to_string()), Some(&3));
+        assert_eq!(heap.priority(&"f".to_string()), None);
+
+        assert_eq!(heap.top(), "a");
+        assert!(!heap.contains(&"a".to_string()));
+        assert_eq!(heap.priority(&"e".to_string()), Some(&4));
+    }
+
+    #[test]
+    fn remove() {
+        type Item = u32;
+
+        let mut heap = IndexedHeap::<Item>::new();
+        assert_eq!(heap.remove(1), Err(HeapError::Empty));
+
+        for value in 0..10 {
+            heap.insert(value, 10 - value as Priority);
+        }
+
+        assert_eq!(heap.remove(10), Err(HeapError::NotFound));
+
+        let node = heap.remove(4).unwrap();
+        assert_eq!(node.value, 4);
+        assert_eq!(node.priority, 6);
+        assert!(!heap.contains(&4));
+        assert_eq!(heap.remove(4), Err(HeapError::NotFound));
+
+        assert!(heap.remove(9).is_ok());
+        assert!(heap.remove(0).is_ok());
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, vec![8, 7, 6, 5, 3, 2, 1]);
+    }
+
+    #[test]
+    fn update() {
+        type Item = usize;
+
+        let mut heap = IndexedHeap::<Item, 4>::new();
+        assert_eq!(heap.update(1, 1), Err(HeapError::Empty));
+
+        for value in 0..20 {
+            heap.insert(value, value as Priority + 10);
+        }
+
+        assert_eq!(heap.update(20, 0), Err(HeapError::NotFound));
+
+        // decrease-key as in Dijkstra
+        assert_eq!(heap.update(15, 1), Ok(()));
+        assert_eq!(heap.update(7, 2), Ok(()));
+        // and the other way round
+        assert_eq!(heap.update(0, 100), Ok(()));
+
+        assert_eq!(heap.top(), 15);
+        assert_eq!(heap.top(), 7);
+        assert_eq!(heap.top(), 1);
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order.len(), 17);
+        assert_eq!(order.last(), Some(&0));
+        assert!(order[..16].windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn max_order() {
+        type Item = i32;
+
+        let mut heap = IndexedHeap::<Item, 2, MaxOrder>::new();
+        for value in [3, 1, 4, 5, 9, 2, 6] {
+            heap.insert(value, value as Priority);
+        }
+
+        assert_eq!(heap.update(9, 0), Ok(()));
+        assert_eq!(heap.top(), 6);
+        assert_eq!(heap.top(), 5);
+        assert_eq!(heap.top(), 4);
+        assert_eq!(heap.priority(&9), Some(&0));
+    }
+
+    #[test]
+    fn without_clone() {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Item(u32);
+
+        let mut heap = IndexedHeap::<Item>::new();
+        for value in 0..50 {
+            heap.insert(Item(value), (value * 7 % 50) as Priority);
+        }
+
+        assert_eq!(heap.update(Item(49), 100), Ok(()));
+        assert_eq!(heap.remove(Item(0)).map(|node| node.value), Ok(Item(0)));
+        assert_eq!(heap.remove(Item(0)), Err(HeapError::NotFound));
+        assert_eq!(heap.try_peek(), Some(&Item(43)));
+        assert_eq!(heap.priority(&Item(49)), Some(&100));
+
+        let mut last = 0;
+        while let Some(value) = heap.pop() {
+            assert!(!heap.contains(&value));
+            last = value.0;
+        }
+        assert_eq!(last, 49);
+    }
+}
@@ -0,0 +1,124 @@
+use heap::{Heap, MaxOrder, Node, Priority};
+
+#[cfg(test)]
+mod iter_tests {
+
+    use super::*;
+
+    fn sample<const D: heap::Index>() -> Heap<char, D> {
+        let mut heap = Heap::<char, D>::new();
+        for (value, priority) in [('d', 4), ('b', 2), ('e', 5), ('a', 1), ('c', 3)] {
+            heap.insert(value, priority);
+        }
+        heap
+    }
+
+    #[test]
+    fn iter() {
+        let heap = sample::<2>();
+
+        let mut values: Vec<char> = heap.iter().map(|node| node.value).collect();
+        values.sort();
+        assert_eq!(values, vec!['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(heap.iter().len(), 5);
+
+        let sum: Priority = (&heap).into_iter().map(|node| node.priority).sum();
+        assert_eq!(sum, 15);
+        assert_eq!(heap.len(), 5);
+    }
+
+    #[test]
+    fn drain() {
+        let mut heap = sample::<3>();
+        let handle = heap.insert('f', 0);
+
+        let mut nodes: Vec<Node<char>> = heap.drain().collect();
+        nodes.sort_by_key(|node| node.priority);
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes[0].value, 'f');
+
+        assert!(heap.is_empty());
+        assert!(heap.get_by_handle(handle).is_none());
+
+        heap.insert('g', 7);
+        assert_eq!(heap.top(), 'g');
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut heap = sample::<2>();
+
+        let values: Vec<char> = heap.drain_sorted().map(|node| node.value).collect();
+        assert_eq!(values, vec!['a', 'b', 'c', 'd', 'e']);
+        assert!(heap.is_empty());
+
+        // dropped early the rest is removed as well
+        let mut heap = sample::<2>();
+        let first: Vec<char> = heap.drain_sorted().take(2).map(|node| node.value).collect();
+        assert_eq!(first, vec!['a', 'b']);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let heap = sample::<4>();
+        let values: Vec<char> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|node| node.value)
+            .collect();
+        assert_eq!(values, vec!['a', 'b', 'c', 'd', 'e']);
+
+        let heap: Heap<u32, 2, MaxOrder> =
+            (0..100).map(|i| (i, (i * 37 % 100) as Priority)).collect();
+        let priorities: Vec<Priority> = heap
+            .into_sorted_vec()
+            .iter()
+            .map(|node| node.priority)
+            .collect();
+        assert_eq!(priorities, (0..100).rev().collect::<Vec<Priority>>());
+
+        assert!(Heap::<u32>::new().into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn into_iter() {
+        let heap = sample::<2>();
+
+        let mut values: Vec<char> = heap.into_iter().map(|node| node.value).collect();
+        values.sort();
+        assert_eq!(values, vec!['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut heap: Heap<String, 3> = ["x", "y", "z"]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.to_string(), 10 - i as Priority))
+            .collect();
+
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.top(), "z");
+        assert_eq!(heap.top(), "y");
+        assert_eq!(heap.top(), "x");
+    }
+
+    #[test]
+    fn extend() {
+        let mut heap = sample::<2>();
+        let handle = heap.insert('z', 26);
+
+        // a few nodes are sifted one by one
+        heap.extend([('y', 0)]);
+        assert_eq!(heap.peek(), 'y');
+
+        // a big batch is heapified at once
+        heap.extend((0..100).map(|i| ('x', 100 + i)));
+        assert_eq!(heap.len(), 107);
+        assert_eq!(heap.get_by_handle(handle).unwrap().priority, 26);
+
+        let priorities: Vec<Priority> = heap.drain_sorted().map(|node| node.priority).collect();
+        assert!(priorities.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
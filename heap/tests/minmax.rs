@@ -0,0 +1,87 @@
+use heap::MinMaxHeap;
+
+mod common;
+
+#[cfg(test)]
+mod minmax_tests {
+
+    use super::*;
+
+    #[test]
+    fn is_empty() {
+        type Item = i32;
+
+        let mut heap = MinMaxHeap::<Item>::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek_min(), None);
+        assert_eq!(heap.peek_max(), None);
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.pop_max(), None);
+
+        heap.insert(1, 0);
+        assert!(!heap.is_empty());
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&1));
+        assert_eq!(heap.pop_max(), Some(1));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn both_ends() {
+        type Item = char;
+
+        let mut heap = MinMaxHeap::<Item>::new();
+        for (value, priority) in [('d', 4), ('b', 2), ('f', 6), ('a', 1), ('e', 5), ('c', 3)] {
+            heap.insert(value, priority);
+        }
+
+        assert_eq!(heap.peek_min(), Some(&'a'));
+        assert_eq!(heap.peek_max(), Some(&'f'));
+        assert_eq!(heap.pop_min(), Some('a'));
+        assert_eq!(heap.pop_max(), Some('f'));
+        assert_eq!(heap.pop_max(), Some('e'));
+        assert_eq!(heap.pop_min(), Some('b'));
+        assert_eq!(heap.pop_min(), Some('c'));
+        assert_eq!(heap.pop_max(), Some('d'));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn random() {
+        type Item = u64;
+
+        let mut next = common::lcg(42);
+
+        let mut heap = MinMaxHeap::<Item>::new();
+        let mut kept: Vec<Item> = vec![];
+
+        for _ in 0..3000 {
+            match next() % 4 {
+                0 | 1 => {
+                    let priority = next() % 500;
+                    heap.insert(priority, priority);
+                    kept.push(priority);
+                }
+                2 => {
+                    let min = kept.iter().copied().min();
+                    if let Some(min) = min {
+                        kept.remove(kept.iter().position(|&v| v == min).unwrap());
+                    }
+                    assert_eq!(heap.pop_min(), min);
+                }
+                _ => {
+                    let max = kept.iter().copied().max();
+                    if let Some(max) = max {
+                        kept.remove(kept.iter().position(|&v| v == max).unwrap());
+                    }
+                    assert_eq!(heap.pop_max(), max);
+                }
+            }
+
+            assert_eq!(heap.len(), kept.len());
+            assert_eq!(heap.peek_min(), kept.iter().min());
+            assert_eq!(heap.peek_max(), kept.iter().max());
+        }
+    }
+}
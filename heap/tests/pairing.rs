@@ -0,0 +1,59 @@
+use heap::{HeapError, PairingHeap};
+
+#[macro_use]
+mod common;
+
+#[cfg(test)]
+mod pairing_tests {
+
+    use super::*;
+
+    handle_heap_tests!(PairingHeap);
+
+    #[test]
+    fn by_value() {
+        type Item = char;
+
+        let mut heap = PairingHeap::<Item>::new();
+        assert_eq!(heap.update('a', 0), Err(HeapError::Empty));
+        assert_eq!(heap.remove('a'), Err(HeapError::Empty));
+
+        for (value, priority) in [('a', 1), ('b', 2), ('c', 3), ('d', 4), ('e', 5)] {
+            heap.insert(value, priority);
+        }
+        // pair the children so the search walks siblings and subtrees
+        assert_eq!(heap.top(), 'a');
+
+        assert_eq!(heap.update('e', 0), Ok(()));
+        assert_eq!(heap.peek(), 'e');
+        assert_eq!(heap.update('x', 0), Err(HeapError::NotFound));
+
+        let node = heap.remove('c').unwrap();
+        assert_eq!((node.value, node.priority), ('c', 3));
+        assert_eq!(heap.remove('c'), Err(HeapError::NotFound));
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, vec!['e', 'b', 'd']);
+    }
+
+    #[test]
+    fn lower_root() {
+        type Item = u32;
+
+        // the lowered root goes below its paired children
+        let mut heap = PairingHeap::<Item>::new();
+        let handles: Vec<_> = (0..16)
+            .map(|i| heap.insert(i, i as heap::Priority))
+            .collect();
+        assert_eq!(heap.top(), 0);
+
+        assert_eq!(heap.update_by_handle(handles[1], 100), Ok(()));
+        assert_eq!(heap.peek(), 2);
+        assert_eq!(heap.update_by_handle(handles[2], 50), Ok(()));
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        let mut expected: Vec<Item> = (3..16).collect();
+        expected.extend([2, 1]);
+        assert_eq!(order, expected);
+    }
+}
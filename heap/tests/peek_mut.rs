@@ -0,0 +1,64 @@
+use heap::{Heap, MaxOrder, PeekMut, Priority};
+
+#[cfg(test)]
+mod peek_mut_tests {
+
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut heap = Heap::<i32>::new();
+        assert!(heap.peek_mut().is_none());
+    }
+
+    #[test]
+    fn change_priority() {
+        type Item = i32;
+
+        let mut heap = Heap::<Item, 3>::new();
+        let handles: Vec<_> = (1..=6).map(|i| heap.insert(i, i as Priority)).collect();
+
+        if let Some(mut top) = heap.peek_mut() {
+            assert_eq!(top.value, 1);
+            top.priority = 10;
+        }
+
+        assert_eq!(heap.peek(), 2);
+        assert_eq!(heap.get_by_handle(handles[0]).unwrap().priority, 10);
+
+        // read only access keeps the heap as it is
+        assert_eq!(heap.peek_mut().map(|top| top.priority), Some(2));
+
+        let order: Vec<Item> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, vec![2, 3, 4, 5, 6, 1]);
+    }
+
+    #[test]
+    fn change_value() {
+        let mut heap = Heap::<String, 2, MaxOrder>::new();
+        heap.insert("a".to_string(), 1);
+        heap.insert("b".to_string(), 2);
+
+        heap.peek_mut().unwrap().value.push('!');
+        assert_eq!(heap.top(), "b!");
+        assert_eq!(heap.top(), "a");
+    }
+
+    #[test]
+    fn pop() {
+        let mut heap = Heap::<char>::new();
+        let a = heap.insert('a', 1);
+        heap.insert('b', 2);
+        heap.insert('c', 3);
+
+        let top = heap.peek_mut().unwrap();
+        let node = PeekMut::pop(top);
+        assert_eq!(node.value, 'a');
+        assert_eq!(node.priority, 1);
+
+        assert!(heap.get_by_handle(a).is_none());
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.top(), 'b');
+        assert_eq!(heap.top(), 'c');
+    }
+}
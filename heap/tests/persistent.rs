@@ -0,0 +1,138 @@
+use heap::{LeftistHeap, MaxOrder, PersistentHeap, Priority, Shape, SkewHeap};
+
+#[cfg(test)]
+mod persistent_tests {
+
+    use super::*;
+
+    fn versions<S: Shape>() {
+        type Item = char;
+
+        let empty = PersistentHeap::<Item, S>::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.try_peek(), None);
+        assert!(empty.pop().is_none());
+
+        let one = empty.insert('b', 2);
+        let two = one.insert('a', 1);
+        let three = two.insert('c', 3);
+
+        // older versions are not touched
+        assert!(empty.is_empty());
+        assert_eq!(one.len(), 1);
+        assert_eq!(one.peek(), 'b');
+        assert_eq!(two.len(), 2);
+        assert_eq!(two.peek(), 'a');
+        assert_eq!(three.len(), 3);
+
+        let (value, rest) = three.top();
+        assert_eq!(value, 'a');
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest.peek(), 'b');
+        assert_eq!(three.peek(), 'a');
+
+        let (value, rest) = rest.pop().unwrap();
+        assert_eq!(*value, 'b');
+        assert_eq!(rest.peek(), 'c');
+        assert_eq!(two.peek(), 'a');
+    }
+
+    fn sorted<S: Shape>() {
+        type Item = u32;
+
+        let mut heap = PersistentHeap::<Item, S, MaxOrder>::new();
+        for i in 0..200 {
+            heap = heap.insert(i, (i * 71 % 200) as Priority);
+        }
+        let snapshot = heap.clone();
+
+        let mut priorities = vec![];
+        while let Some((&value, rest)) = heap.pop() {
+            priorities.push((value * 71 % 200) as Priority);
+            heap = rest;
+        }
+
+        assert_eq!(priorities, (0..200).rev().collect::<Vec<Priority>>());
+        assert_eq!(snapshot.len(), 200);
+        assert_eq!(snapshot.peek() * 71 % 200, 199);
+    }
+
+    fn merge<S: Shape>() {
+        type Item = u32;
+
+        let evens = (0..10)
+            .step_by(2)
+            .fold(PersistentHeap::<Item, S>::new(), |heap, i| {
+                heap.insert(i, i as Priority)
+            });
+        let odds = (1..10)
+            .step_by(2)
+            .fold(PersistentHeap::<Item, S>::new(), |heap, i| {
+                heap.insert(i, i as Priority)
+            });
+
+        let mut all = evens.merge(&odds);
+        assert_eq!(all.len(), 10);
+        assert_eq!(evens.len(), 5);
+        assert_eq!(odds.len(), 5);
+
+        for i in 0..10 {
+            let (value, rest) = all.top();
+            assert_eq!(value, i);
+            all = rest;
+        }
+        assert!(all.is_empty());
+    }
+
+    fn deep<S: Shape>() {
+        type Item = u32;
+
+        // ascending inserts make long spines, drop must not overflow the stack
+        let mut heap = PersistentHeap::<Item, S, MaxOrder>::new();
+        for i in 0..200_000 {
+            heap = heap.insert(i, i as Priority);
+        }
+        assert_eq!(heap.peek(), 199_999);
+    }
+
+    #[test]
+    fn leftist() {
+        versions::<heap::Leftist>();
+        sorted::<heap::Leftist>();
+        merge::<heap::Leftist>();
+        deep::<heap::Leftist>();
+
+        let heap = LeftistHeap::<&str>::default().insert("x", 1);
+        assert_eq!(heap.peek(), "x");
+    }
+
+    #[test]
+    fn skew() {
+        versions::<heap::Skew>();
+        sorted::<heap::Skew>();
+        merge::<heap::Skew>();
+        deep::<heap::Skew>();
+
+        let heap = SkewHeap::<&str>::default().insert("x", 1);
+        assert_eq!(heap.peek(), "x");
+    }
+
+    #[test]
+    fn backtracking() {
+        // the snapshot before each choice is the heap itself
+        let start = LeftistHeap::<&str>::new()
+            .insert("a", 3)
+            .insert("b", 1)
+            .insert("c", 2);
+
+        let (first, rest) = start.top();
+        let branch_1 = rest.insert("d", 0);
+        let branch_2 = rest.insert("e", 5);
+
+        assert_eq!(first, "b");
+        assert_eq!(branch_1.peek(), "d");
+        assert_eq!(branch_2.peek(), "c");
+        assert_eq!(rest.len(), 2);
+        assert_eq!(start.len(), 3);
+    }
+}
@@ -0,0 +1,99 @@
+use heap::{Heap, HeapError, Priority, RunningMedian, RunningQuantile};
+
+mod common;
+
+#[cfg(test)]
+mod quantile_tests {
+
+    use super::*;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn median() {
+        let mut median = RunningMedian::<Priority>::new();
+        assert!(median.is_empty());
+        assert_eq!(median.median(), None);
+
+        let mut handles = vec![];
+        for (sample, expected) in [(5, 5), (1, 1), (9, 5), (3, 3), (7, 5)] {
+            handles.push(median.insert(sample));
+            assert_eq!(median.median(), Some(&expected));
+        }
+        assert_eq!(median.len(), 5);
+
+        assert_eq!(median.remove(handles[2]), Ok(9));
+        assert_eq!(median.remove(handles[2]), Err(HeapError::InvalidHandle));
+        // 1 3 5 7, the lower middle one
+        assert_eq!(median.median(), Some(&3));
+        assert_eq!(median.remove(handles[1]), Ok(1));
+        assert_eq!(median.median(), Some(&5));
+    }
+
+    #[test]
+    fn foreign_handle() {
+        let mut median = RunningMedian::<Priority>::new();
+        let mut other = RunningMedian::<Priority>::new();
+        let mut heap = Heap::<Priority>::new();
+        median.insert(5);
+        let foreign = [other.insert(7), heap.insert(9, 9)];
+
+        // the same slot in another estimator or heap
+        for handle in foreign {
+            assert_eq!(median.remove(handle), Err(HeapError::InvalidHandle));
+        }
+        assert_eq!(median.len(), 1);
+        assert_eq!(median.median(), Some(&5));
+        assert_eq!(other.median(), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile out of 0..=1")]
+    fn out_of_range() {
+        RunningQuantile::<Priority>::new(1.5);
+    }
+
+    #[test]
+    fn extremes() {
+        let mut min = RunningQuantile::<i32>::new(0.0);
+        let mut max = RunningQuantile::<i32>::new(1.0);
+        assert_eq!(max.q(), 1.0);
+        for sample in [4, -2, 8, 0] {
+            min.insert(sample);
+            max.insert(sample);
+        }
+        assert_eq!(min.quantile(), Some(&-2));
+        assert_eq!(max.quantile(), Some(&8));
+    }
+
+    #[test]
+    fn sliding_window() {
+        let mut next = common::lcg(42);
+
+        const WINDOW: usize = 50;
+        let qs = [0.0, 0.1, 0.5, 0.9, 0.99, 1.0];
+        let mut estimators: Vec<RunningQuantile<Priority>> =
+            qs.iter().map(|&q| RunningQuantile::new(q)).collect();
+        let mut window = VecDeque::new();
+
+        for _ in 0..2000 {
+            let sample = next() % 1000;
+            let handles: Vec<_> = estimators.iter_mut().map(|e| e.insert(sample)).collect();
+            window.push_back((sample, handles));
+
+            if window.len() > WINDOW {
+                let (expired, handles) = window.pop_front().unwrap();
+                for (estimator, handle) in estimators.iter_mut().zip(handles) {
+                    assert_eq!(estimator.remove(handle), Ok(expired));
+                }
+            }
+
+            let mut sorted: Vec<Priority> = window.iter().map(|(sample, _)| *sample).collect();
+            sorted.sort_unstable();
+            for (estimator, &q) in estimators.iter().zip(&qs) {
+                let rank = ((sorted.len() - 1) as f64 * q).floor() as usize;
+                assert_eq!(estimator.quantile(), Some(&sorted[rank]));
+                assert_eq!(estimator.len(), sorted.len());
+            }
+        }
+    }
+}
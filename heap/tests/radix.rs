@@ -0,0 +1,115 @@
+use heap::{HeapError, Priority, RadixHeap};
+
+mod common;
+
+#[cfg(test)]
+mod radix_tests {
+
+    use super::*;
+
+    #[test]
+    fn is_empty() {
+        type Item = i32;
+
+        let mut heap = RadixHeap::<Item>::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.try_peek(), None);
+        assert_eq!(heap.pop(), None);
+
+        assert_eq!(heap.insert(1, 0), Ok(()));
+        assert!(!heap.is_empty());
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty heap")]
+    fn top_empty() {
+        let mut heap = RadixHeap::<i32>::new();
+        heap.top();
+    }
+
+    #[test]
+    fn top() {
+        type Item = i32;
+
+        let mut heap = RadixHeap::<Item>::new();
+        for (value, priority) in [(4, 40), (2, 20), (3, 30), (1, 10), (5, 50)] {
+            heap.insert(value, priority).unwrap();
+        }
+
+        assert_eq!(heap.peek(), 1);
+        assert_eq!(heap.top(), 1);
+        assert_eq!(heap.last(), 10);
+        assert_eq!(heap.top(), 2);
+        assert_eq!(heap.top(), 3);
+        assert_eq!(heap.top(), 4);
+        assert_eq!(heap.top(), 5);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn peek_ties() {
+        type Item = char;
+
+        let mut heap = RadixHeap::<Item>::new();
+        for (value, priority) in [('a', 7), ('b', 5), ('c', 9), ('d', 5), ('e', 7)] {
+            heap.insert(value, priority).unwrap();
+        }
+
+        // peek shows the node pop takes among the equal priorities
+        while let Some(&value) = heap.try_peek() {
+            assert_eq!(heap.pop(), Some(value));
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn below_last() {
+        let mut heap = RadixHeap::<char>::new();
+        heap.insert('b', 20).unwrap();
+        heap.insert('c', 30).unwrap();
+        assert_eq!(heap.pop(), Some('b'));
+
+        assert_eq!(heap.insert('a', 19), Err(HeapError::BelowLast));
+        // equal to the last removed one is fine
+        assert_eq!(heap.insert('x', 20), Ok(()));
+        assert_eq!(heap.pop(), Some('x'));
+        assert_eq!(heap.pop(), Some('c'));
+        assert_eq!(heap.len(), 0);
+    }
+
+    #[test]
+    fn simulation() {
+        type Item = u64;
+
+        let mut next = common::lcg(42);
+
+        let mut heap = RadixHeap::<Item>::new();
+        let mut kept: Vec<Priority> = vec![];
+
+        for event in 0..3000 {
+            if !next().is_multiple_of(3) {
+                // new events are scheduled after the current time
+                let priority = heap.last() + next() % 10_000;
+                heap.insert(event, priority).unwrap();
+                kept.push(priority);
+            } else {
+                kept.sort_unstable_by(|a, b| b.cmp(a));
+                let expected = kept.pop();
+                assert_eq!(heap.pop().is_some(), expected.is_some());
+                if let Some(expected) = expected {
+                    assert_eq!(heap.last(), expected);
+                }
+            }
+            assert_eq!(heap.len(), kept.len());
+        }
+
+        // large priorities use the highest buckets
+        heap.insert(0, Priority::MAX).unwrap();
+        while heap.len() > 1 {
+            heap.pop();
+        }
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.last(), Priority::MAX);
+    }
+}
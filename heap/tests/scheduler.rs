@@ -0,0 +1,177 @@
+use heap::{Clock, Heap, HeapError, MockClock, Node, Priority, Scheduler, TimingWheel};
+
+mod common;
+
+#[cfg(test)]
+mod scheduler_tests {
+
+    use super::*;
+
+    fn tasks<T>(nodes: impl IntoIterator<Item = Node<T>>) -> Vec<T> {
+        nodes.into_iter().map(|node| node.value).collect()
+    }
+
+    #[test]
+    fn mock_clock() {
+        let clock = MockClock::new(10);
+        assert_eq!(clock.now(), 10);
+        clock.advance(5);
+        assert_eq!(clock.now(), 15);
+        clock.set(3);
+        assert_eq!(clock.now(), 3);
+    }
+
+    #[test]
+    fn scheduler() {
+        type Item = &'static str;
+
+        let clock = MockClock::new(0);
+        let mut scheduler = Scheduler::<Item>::new();
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.next_deadline(), None);
+
+        scheduler.schedule(30, "c");
+        let b = scheduler.schedule(20, "b");
+        let a = scheduler.schedule(10, "a");
+        let d = scheduler.schedule(40, "d");
+        assert_eq!(scheduler.len(), 4);
+        assert_eq!(scheduler.next_deadline(), Some(10));
+
+        assert!(tasks(scheduler.poll(&clock)).is_empty());
+
+        clock.advance(25);
+        assert_eq!(tasks(scheduler.poll(&clock)), vec!["a", "b"]);
+        assert_eq!(scheduler.deadline(a), None);
+        assert_eq!(scheduler.cancel(b), Err(HeapError::InvalidHandle));
+
+        assert_eq!(scheduler.reschedule(d, 26), Ok(()));
+        assert_eq!(scheduler.deadline(d), Some(26));
+        clock.advance(1);
+        assert_eq!(tasks(scheduler.poll(&clock)), vec!["d"]);
+
+        let c = scheduler.schedule(100, "e");
+        let node = scheduler.cancel(c).unwrap();
+        assert_eq!((node.value, node.priority), ("e", 100));
+
+        // expired tasks not yielded stay scheduled
+        assert_eq!(scheduler.expired(1000).next().unwrap().value, "c");
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn wheel() {
+        type Item = &'static str;
+
+        let clock = MockClock::new(1000);
+        let mut wheel = TimingWheel::<Item>::new(clock.now());
+        assert!(wheel.is_empty());
+
+        wheel.schedule(1030, "c");
+        let b = wheel.schedule(1020, "b");
+        wheel.schedule(1010, "a");
+        let d = wheel.schedule(5000, "d");
+        wheel.schedule(900, "past");
+        assert_eq!(wheel.len(), 5);
+
+        assert_eq!(tasks(wheel.poll(&clock)), vec!["past"]);
+
+        clock.advance(25);
+        assert_eq!(tasks(wheel.poll(&clock)), vec!["a", "b"]);
+        assert_eq!(wheel.now(), 1025);
+        assert_eq!(wheel.deadline(b), None);
+        assert_eq!(wheel.cancel(b), Err(HeapError::InvalidHandle));
+
+        assert_eq!(wheel.reschedule(d, 1026), Ok(()));
+        assert_eq!(wheel.deadline(d), Some(1026));
+        clock.advance(1);
+        assert_eq!(tasks(wheel.poll(&clock)), vec!["d"]);
+
+        let e = wheel.schedule(Priority::MAX, "e");
+        let node = wheel.cancel(e).unwrap();
+        assert_eq!((node.value, node.priority), ("e", Priority::MAX));
+
+        // the time does not go back
+        assert!(wheel.expired(0).is_empty());
+        assert_eq!(tasks(wheel.expired(1_000_000)), vec!["c"]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn foreign_handle() {
+        type Item = &'static str;
+
+        let mut wheel = TimingWheel::<Item>::new(0);
+        let mut other = TimingWheel::<Item>::new(0);
+        let mut scheduler = Scheduler::<Item>::new();
+        let mut heap = Heap::<Item>::new();
+        wheel.schedule(10, "wheel");
+        scheduler.schedule(10, "scheduler");
+        let foreign = [
+            other.schedule(10, "other"),
+            heap.insert("heap", 10),
+            scheduler.schedule(20, "scheduler"),
+        ];
+
+        // the same slot in another wheel or heap
+        for handle in foreign {
+            assert_eq!(wheel.deadline(handle), None);
+            assert_eq!(wheel.reschedule(handle, 0), Err(HeapError::InvalidHandle));
+            assert_eq!(wheel.cancel(handle), Err(HeapError::InvalidHandle));
+        }
+        assert_eq!(wheel.len(), 1);
+
+        let in_wheel = wheel.schedule(20, "wheel");
+        assert_eq!(scheduler.cancel(in_wheel), Err(HeapError::InvalidHandle));
+        assert_eq!(scheduler.len(), 2);
+    }
+
+    #[test]
+    fn wheel_matches_scheduler() {
+        type Item = u64;
+
+        let mut next = common::lcg(42);
+
+        let clock = MockClock::new(0);
+        let mut scheduler = Scheduler::<Item, 4>::new();
+        let mut wheel = TimingWheel::<Item>::new(clock.now());
+        let mut handles = vec![];
+
+        for task in 0..5000 {
+            match next() % 6 {
+                0..=2 => {
+                    // spans from the next tick to several wheel levels ahead
+                    let delay = next() % (1 << (next() % 24));
+                    let deadline = clock.now() + delay;
+                    handles.push((
+                        scheduler.schedule(deadline, task),
+                        wheel.schedule(deadline, task),
+                    ));
+                }
+                3 if !handles.is_empty() => {
+                    let i = (next() as usize) % handles.len();
+                    let (s, w) = handles.swap_remove(i);
+                    assert_eq!(scheduler.cancel(s).ok(), wheel.cancel(w).ok());
+                }
+                4 if !handles.is_empty() => {
+                    let i = (next() as usize) % handles.len();
+                    let deadline = clock.now() + next() % 5000;
+                    let (s, w) = handles[i];
+                    assert_eq!(
+                        scheduler.reschedule(s, deadline).is_ok(),
+                        wheel.reschedule(w, deadline).is_ok()
+                    );
+                }
+                _ => {
+                    clock.advance(next() % 3000);
+                    let mut expected: Vec<Node<Item>> = scheduler.poll(&clock).collect();
+                    let mut expired = wheel.poll(&clock);
+                    // equal deadlines may come in any order
+                    expected.sort_by_key(|node| (node.priority, node.value));
+                    expired.sort_by_key(|node| (node.priority, node.value));
+                    assert_eq!(expired, expected);
+                }
+            }
+            assert_eq!(wheel.len(), scheduler.len());
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(all(test, feature = "serde"))]
+mod serialize_tests {
+
+    use heap::{Heap, MaxOrder, Node, Priority};
+
+    #[test]
+    fn node() {
+        let node = Node {
+            value: "a".to_string(),
+            priority: 3 as Priority,
+        };
+        let json = serde_json::to_string(&node).unwrap();
+        assert_eq!(json, r#"{"value":"a","priority":3}"#);
+        assert_eq!(serde_json::from_str::<Node<String>>(&json).unwrap(), node);
+    }
+
+    #[test]
+    fn round_trip() {
+        type Item = u32;
+
+        let heap: Heap<Item, 4, MaxOrder> =
+            (0..40).map(|i| (i, (i * 7 % 40) as Priority)).collect();
+        let json = serde_json::to_string(&heap).unwrap();
+
+        let mut loaded: Heap<Item, 4, MaxOrder> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.len(), 40);
+        let original: Vec<Priority> = heap.iter().map(|node| node.priority).collect();
+        let kept: Vec<Priority> = loaded.iter().map(|node| node.priority).collect();
+        assert_eq!(kept, original);
+        assert_eq!(loaded.top(), 17);
+    }
+
+    #[test]
+    fn invalid() {
+        let json = r#"[{"value":0,"priority":5},{"value":1,"priority":1}]"#;
+        let error = serde_json::from_str::<Heap<u32>>(json).unwrap_err();
+        assert!(error.to_string().contains("heap order violated"));
+
+        assert!(serde_json::from_str::<Heap<u32, 2, MaxOrder>>(json).is_ok());
+    }
+}
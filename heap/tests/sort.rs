@@ -0,0 +1,83 @@
+use heap::{heap_sort, kmerge, MaxOrder, MinOrder, Node, OrdF64};
+
+mod common;
+
+#[cfg(test)]
+mod sort_tests {
+
+    use super::*;
+
+    #[test]
+    fn heap_sort_ascending() {
+        let mut data: Vec<u32> = vec![5, 3, 9, 1, 7, 3, 0, 8];
+        heap_sort::<_, _, 2>(&mut data, &MinOrder);
+        assert_eq!(data, vec![0, 1, 3, 3, 5, 7, 8, 9]);
+
+        heap_sort::<_, _, 3>(&mut data, &MaxOrder);
+        assert_eq!(data, vec![9, 8, 7, 5, 3, 3, 1, 0]);
+
+        let mut empty: Vec<u32> = vec![];
+        heap_sort::<_, _, 2>(&mut empty, &MinOrder);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn heap_sort_random() {
+        let mut next = common::lcg(42);
+
+        for len in [1, 2, 3, 10, 100, 1000] {
+            let data: Vec<u64> = (0..len).map(|_| next() % 500).collect();
+            let mut expected = data.clone();
+            expected.sort_unstable();
+
+            let mut binary = data.clone();
+            heap_sort::<_, _, 2>(&mut binary, &MinOrder);
+            assert_eq!(binary, expected);
+
+            let mut quaternary = data;
+            heap_sort::<_, _, 4>(&mut quaternary, &MinOrder);
+            assert_eq!(quaternary, expected);
+        }
+    }
+
+    #[test]
+    fn heap_sort_closure() {
+        let mut nodes: Vec<Node<char, OrdF64>> = [('b', 2.5), ('a', -1.0), ('c', 10.0)]
+            .into_iter()
+            .map(|(value, priority)| Node {
+                value,
+                priority: OrdF64(priority),
+            })
+            .collect();
+
+        let by_priority = |a: &Node<char, OrdF64>, b: &Node<char, OrdF64>| a.priority < b.priority;
+        heap_sort::<_, _, 2>(&mut nodes, &by_priority);
+
+        let values: Vec<char> = nodes.iter().map(|node| node.value).collect();
+        assert_eq!(values, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn kmerge_sorted() {
+        let runs = vec![vec![1, 4, 7], vec![2, 5, 8], vec![], vec![0, 3, 6, 9]];
+
+        let merged = kmerge::<_, _, 2>(runs.clone(), MinOrder);
+        assert_eq!(merged.size_hint(), (10, Some(10)));
+        assert_eq!(merged.collect::<Vec<i32>>(), (0..10).collect::<Vec<i32>>());
+
+        let descending = runs.into_iter().map(|run| run.into_iter().rev());
+        let merged: Vec<i32> = kmerge::<_, _, 4>(descending, MaxOrder).collect();
+        assert_eq!(merged, (0..10).rev().collect::<Vec<i32>>());
+
+        let mut empty = kmerge::<Vec<i32>, _, 2>(vec![], MinOrder);
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn kmerge_many() {
+        // every tenth number in each of the runs
+        let runs = (0..10).map(|start| (start..1000).step_by(10));
+        let merged: Vec<u32> = kmerge::<_, _, 3>(runs, MinOrder).collect();
+        assert_eq!(merged, (0..1000).collect::<Vec<u32>>());
+    }
+}